@@ -1,3 +1,5 @@
+pub mod symmetry;
+
 use crate::grid::Grid;
 use crate::solver::strategies::{Deduction, Strategy};
 
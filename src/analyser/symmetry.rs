@@ -0,0 +1,113 @@
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellIdx;
+use crate::grid::transforms::{reflected_cell, rotated_cell, Axis};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    QuarterTurn,
+    HalfTurn,
+    Reflection(Axis),
+}
+
+impl Symmetry {
+
+    pub fn all() -> Vec<Symmetry> {
+        vec![
+            Symmetry::QuarterTurn,
+            Symmetry::HalfTurn,
+            Symmetry::Reflection(Axis::Horizontal),
+            Symmetry::Reflection(Axis::Vertical),
+            Symmetry::Reflection(Axis::Diagonal),
+            Symmetry::Reflection(Axis::AntiDiagonal),
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Symmetry::QuarterTurn => "90° rotational",
+            Symmetry::HalfTurn => "180° rotational",
+            Symmetry::Reflection(Axis::Horizontal) => "horizontal mirror",
+            Symmetry::Reflection(Axis::Vertical) => "vertical mirror",
+            Symmetry::Reflection(Axis::Diagonal) => "diagonal",
+            Symmetry::Reflection(Axis::AntiDiagonal) => "anti-diagonal",
+        }
+    }
+
+    pub fn map_cell<const N: usize>(&self, cell: CellIdx<N>) -> CellIdx<N> {
+        match *self {
+            Symmetry::QuarterTurn => rotated_cell(cell, 1),
+            Symmetry::HalfTurn => rotated_cell(cell, 2),
+            Symmetry::Reflection(axis) => reflected_cell(cell, axis),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymmetryReport<const N: usize> {
+    pub symmetry: Symmetry,
+    pub automorphism: Option<Vec<Candidate<N>>>,
+}
+
+pub fn symmetries<const N: usize>(grid: &Grid<N>) -> Vec<SymmetryReport<N>> {
+    Symmetry::all().into_iter()
+        .filter(|symmetry| grid.preserves_layout(|cell| symmetry.map_cell(cell)))
+        .filter(|symmetry| grid.cells().iter().all(|cell| grid.is_empty(cell) == grid.is_empty(symmetry.map_cell(cell))))
+        .map(|symmetry| SymmetryReport { symmetry, automorphism: automorphism(grid, symmetry) })
+        .collect()
+}
+
+pub fn automorphisms<const N: usize>(grid: &Grid<N>) -> Vec<(Symmetry, Vec<Candidate<N>>)> {
+    symmetries(grid).into_iter()
+        .filter_map(|report| report.automorphism.map(|digits| (report.symmetry, digits)))
+        .collect()
+}
+
+fn automorphism<const N: usize>(grid: &Grid<N>, symmetry: Symmetry) -> Option<Vec<Candidate<N>>> {
+
+    let mut images = vec![None; N + 1];
+    let mut used = vec![false; N + 1];
+    for cell in grid.cells().iter() {
+        if let (Some(Candidate(from)), Some(Candidate(to))) = (grid.value(cell), grid.value(symmetry.map_cell(cell))) {
+            match images[from] {
+                Some(image) if image != to => return None,
+                Some(_) => {},
+                None if used[to] => return None,
+                None => { images[from] = Some(to); used[to] = true; },
+            }
+        }
+    }
+
+    let mut unused = (1 ..= N).filter(|&digit| !used[digit]);
+    Some((1 ..= N).map(|digit| Candidate(images[digit].unwrap_or_else(|| unused.next().unwrap()))).collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::transforms::Axis;
+    use crate::grid::variants::Classic;
+
+    use super::{symmetries, Symmetry};
+
+    #[test]
+    fn test_detects_automorphic_puzzle() {
+        let grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), ".....54..4..8..3...794...62.......7..6.7.3.4..3.......84...613...7..2..6..65.....").unwrap();
+        let reports = symmetries(&grid);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].symmetry, Symmetry::HalfTurn);
+        assert_eq!(reports[0].automorphism, Some((1 ..= 9).rev().map(Candidate).collect()));
+
+        let mirrored = grid.reflect(Axis::Vertical).unwrap();
+        let reports = symmetries(&mirrored);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].symmetry, Symmetry::HalfTurn);
+
+        let broken = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), ".....14..4..8..3...794...62.......7..6.7.3.4..3.......84...613...7..2..6..65.....").unwrap();
+        let reports = symmetries(&broken);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].automorphism, None);
+    }
+}
@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use rand::thread_rng;
 
 use sudoxide::analyser::steps_to_solve;
 use sudoxide::grid::candidate::Candidate;
@@ -14,7 +15,7 @@ fn main() {
     let competition_steps = vec![vec![FullHouse, HiddenSingle, NakedSingle], vec![PointingClaiming], vec![NakedSubset(2), HiddenSubset(2)], vec![NakedSubset(3), HiddenSubset(3)], vec![NakedSubset(4), HiddenSubset(4)]];
     let empty_grid = Grid::<9>::empty_classic();
 
-    for puzzle in generator::generate_puzzles_on_empty_grid_with_pattern(empty_grid.clone(), pattern_cells) {
+    for puzzle in generator::generate_puzzles_on_empty_grid_with_pattern(empty_grid.clone(), pattern_cells, thread_rng()) {
         let clues: Vec<_> = puzzle.iter().map(|&v| if v == 0 { None } else { Some(Candidate(v)) }).collect();
         let grid = Grid::<9>::from_empty_grid_and_clues(empty_grid.clone(), &clues).unwrap();
         if let Some(steps) = steps_to_solve(grid, &competition_steps) {
@@ -2,6 +2,7 @@ use std::io::{self, BufRead};
 
 use itertools::Itertools;
 
+use sudoxide::generator;
 use sudoxide::grid::Grid;
 use sudoxide::grid::variants::Classic;
 use sudoxide::solver;
@@ -19,6 +20,7 @@ fn main() {
         let grid_result = Grid::<N>::from_empty_grid_and_string(Grid::empty_classic(), &line.expect("Failed to read from stdin"));
         if grid_result.is_ok() {
             let mut grid = grid_result.unwrap();
+            if generator::has_unique_solution(&grid) { grid.declare_unique_solution(); }
             println!("\nInitial grid:\n\n{}", grid);
             let solve_details = solver::solve(&mut grid, &all_strategies(N));
             for (step, deductions) in solve_details.steps {
@@ -1,5 +1,11 @@
+use std::cmp::Ordering;
+
+use itertools::{iproduct, Itertools};
+
+use crate::grid::Grid;
+
 pub fn minlex<const N: usize>(puzzle: &[usize]) -> Vec<usize> {
-    
+
     let (mut reverse_lookup, mut seen, mut count) = (vec![0; N + 1], vec![false; N + 1], 0);
     for &clue in puzzle {
         if clue != 0 && !seen[clue] {
@@ -16,3 +22,186 @@ pub fn minlex<const N: usize>(puzzle: &[usize]) -> Vec<usize> {
 
     new_puzzle
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transform {
+    pub transpose: bool,
+    pub rows: [usize; 9],
+    pub columns: [usize; 9],
+    pub digits: [usize; 10],
+}
+
+impl Transform {
+    pub fn apply(&self, puzzle: &[usize]) -> Vec<usize> {
+        iproduct!(0 .. 9, 0 .. 9)
+            .map(|(r, c)| self.digits[clue_at(puzzle, self.transpose, self.rows[r], self.columns[c])])
+            .collect()
+    }
+}
+
+pub fn is_classic<const N: usize>(grid: &Grid<N>) -> bool {
+    N == 9
+        && grid.regions().iter().all(|region| region.iter().map(|cell| (cell.row() / 3, cell.col() / 3)).all_equal())
+        && grid.cells().iter().all(|cell| grid.neighbours(cell).len() == 20)
+}
+
+pub fn classic_minlex(puzzle: &[usize]) -> Vec<usize> {
+    classic_minlex_with_transform(puzzle).0
+}
+
+pub fn classic_minlex_with_transform(puzzle: &[usize]) -> (Vec<usize>, Transform) {
+
+    let mut candidates: Vec<_> = iproduct!([false, true], line_orders())
+        .map(|(transpose, columns)| PartialTransform { transpose, rows: [0; 9], num_rows: 0, columns, digits: [0; 10], next_digit: 1 })
+        .collect();
+
+    let mut canonical_puzzle = Vec::with_capacity(81);
+    for _ in 0 .. 9 {
+        let mut best_row = [usize::MAX; 9];
+        let mut best_candidates = Vec::new();
+
+        for candidate in &candidates {
+            for row in candidate.next_rows() {
+                let extended = candidate.extend(puzzle, row, &mut best_row);
+                match extended {
+                    Some((Ordering::Less, extended)) => { best_candidates.clear(); best_candidates.push(extended); },
+                    Some((_, extended)) => best_candidates.push(extended),
+                    None => {},
+                }
+            }
+        }
+
+        canonical_puzzle.extend(best_row);
+        candidates = best_candidates;
+    }
+
+    (canonical_puzzle, candidates.swap_remove(0).into_transform())
+}
+
+#[derive(Clone)]
+struct PartialTransform {
+    transpose: bool,
+    rows: [usize; 9],
+    num_rows: usize,
+    columns: [usize; 9],
+    digits: [usize; 10],
+    next_digit: usize,
+}
+
+impl PartialTransform {
+
+    fn next_rows(&self) -> Vec<usize> {
+        let used_rows = &self.rows[.. self.num_rows];
+        if self.num_rows.is_multiple_of(3) {
+            let used_bands: Vec<_> = used_rows.iter().map(|row| row / 3).collect();
+            (0 .. 9).filter(|row| !used_bands.contains(&(row / 3))).collect()
+        } else {
+            let band = self.rows[self.num_rows - 1] / 3;
+            (3 * band .. 3 * band + 3).filter(|row| !used_rows.contains(row)).collect()
+        }
+    }
+
+    fn extend(&self, puzzle: &[usize], row: usize, best_row: &mut [usize; 9]) -> Option<(Ordering, PartialTransform)> {
+        let mut extended = self.clone();
+        let mut ordering = Ordering::Equal;
+
+        for (idx, &column) in self.columns.iter().enumerate() {
+            let clue = clue_at(puzzle, self.transpose, row, column);
+            if clue != 0 && extended.digits[clue] == 0 {
+                extended.digits[clue] = extended.next_digit;
+                extended.next_digit += 1;
+            }
+
+            let relabelled = extended.digits[clue];
+            if ordering == Ordering::Equal {
+                ordering = relabelled.cmp(&best_row[idx]);
+                if ordering == Ordering::Greater { return None; }
+            }
+            if ordering == Ordering::Less {
+                best_row[idx] = relabelled;
+            }
+        }
+
+        extended.rows[extended.num_rows] = row;
+        extended.num_rows += 1;
+        Some((ordering, extended))
+    }
+
+    fn into_transform(mut self) -> Transform {
+        for digit in 1 ..= 9 {
+            if self.digits[digit] == 0 {
+                self.digits[digit] = self.next_digit;
+                self.next_digit += 1;
+            }
+        }
+        Transform { transpose: self.transpose, rows: self.rows, columns: self.columns, digits: self.digits }
+    }
+}
+
+fn clue_at(puzzle: &[usize], transpose: bool, row: usize, column: usize) -> usize {
+    if transpose { puzzle[column * 9 + row] } else { puzzle[row * 9 + column] }
+}
+
+fn line_orders() -> Vec<[usize; 9]> {
+    let triples: Vec<Vec<usize>> = (0 .. 3).permutations(3).collect();
+    iproduct!(&triples, &triples, &triples, &triples)
+        .map(|(bands, first, second, third)| {
+            let within = [first, second, third];
+            let mut order = [0; 9];
+            for (idx, &band) in bands.iter().enumerate() {
+                for (offset, &line) in within[idx].iter().enumerate() {
+                    order[3 * idx + offset] = 3 * band + line;
+                }
+            }
+            order
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+
+    use super::{classic_minlex, classic_minlex_with_transform, line_orders, Transform};
+
+    fn random_transform(rng: &mut StdRng) -> Transform {
+        let orders = line_orders();
+        let mut digits = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        digits[1 ..].shuffle(rng);
+        Transform { transpose: rand::Rng::gen(rng), rows: *orders.choose(rng).unwrap(), columns: *orders.choose(rng).unwrap(), digits }
+    }
+
+    #[test]
+    fn test_classic_minlex_is_invariant() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let puzzles = [
+            (
+                "1.23..45.....5..2.53..4.1....6....74....8....92....3....3.7..12.5..3.....74..19.6",
+                "........1.....2.....314.56..1...7.264...2.8.56...89....7.....52.8.5.4.1.9...16..8",
+            ),
+            (
+                ".7..4........3...5...8..1..4.3....2....5.19..6.........5.9..7..2......4..........",
+                "..............1..2..3.4..5.........6.....27.1.85.3......9.8....1......4.7..5.....",
+            ),
+            (
+                ".....54..4..8..3...794...62.......7..6.7.3.4..3.......84...613...7..2..6..65.....",
+                "...........1..2.34..567.2......16..8...3...4..564....1....6...7...9..45.1.45....6",
+            ),
+        ];
+        for (line, expected) in puzzles {
+            let puzzle: Vec<_> = line.bytes().map(|b| if b.is_ascii_digit() { (b - b'0') as usize } else { 0 }).collect();
+            let expected: Vec<_> = expected.bytes().map(|b| if b.is_ascii_digit() { (b - b'0') as usize } else { 0 }).collect();
+            let (canonical_puzzle, transform) = classic_minlex_with_transform(&puzzle);
+            assert_eq!(canonical_puzzle, expected);
+            assert_eq!(transform.apply(&puzzle), expected);
+
+            for _ in 0 .. 5 {
+                let disguised_puzzle = random_transform(&mut rng).apply(&puzzle);
+                assert_eq!(classic_minlex(&disguised_puzzle), expected);
+            }
+        }
+    }
+}
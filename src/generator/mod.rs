@@ -1,16 +1,22 @@
+use brute_force::BruteForceSolver;
 use patterns::PatternPuzzlesIterator;
+use rand::Rng;
 
 use crate::grid::Grid;
 use crate::grid::cell::CellIdx;
 
-mod brute_force;
-mod canonicalisation;
+pub(crate) mod brute_force;
+pub mod canonicalisation;
 mod patterns;
 
-pub fn generate_puzzles_on_empty_grid_with_pattern<const N: usize>(grid: Grid<N>, pattern: Vec<CellIdx<N>>) -> impl Iterator<Item = Vec<usize>> {
-    PatternPuzzlesIterator::for_empty_grid_and_pattern(grid, pattern)
+pub fn generate_puzzles_on_empty_grid_with_pattern<R: Rng, const N: usize>(grid: Grid<N>, pattern: Vec<CellIdx<N>>, rng: R) -> impl Iterator<Item = Vec<usize>> {
+    PatternPuzzlesIterator::for_empty_grid_and_pattern(grid, pattern, rng)
 }
 
-pub fn generate_puzzles_for_starting_grid_with_pattern<const N: usize>(grid: Grid<N>, pattern: Vec<CellIdx<N>>) -> impl Iterator<Item = Vec<usize>> {
-    PatternPuzzlesIterator::for_starting_grid_and_pattern(grid, pattern)
+pub fn generate_puzzles_for_starting_grid_with_pattern<R: Rng, const N: usize>(grid: Grid<N>, pattern: Vec<CellIdx<N>>, rng: R) -> impl Iterator<Item = Vec<usize>> {
+    PatternPuzzlesIterator::for_starting_grid_and_pattern(grid, pattern, rng)
+}
+
+pub fn has_unique_solution<const N: usize>(grid: &Grid<N>) -> bool {
+    BruteForceSolver::for_starting_grid(grid).has_unique_solution(&vec![0; N * N])
 }
@@ -8,14 +8,16 @@ use crate::grid::Grid;
 use crate::grid::cell::CellIdx;
 
 use super::brute_force::BruteForceSolver;
-use super::canonicalisation::minlex;
+use super::canonicalisation::{classic_minlex, is_classic, minlex};
 
 type Pattern<const N: usize> = Vec<CellIdx<N>>;
 type Puzzle = Vec<usize>;
 
-pub struct PatternPuzzlesIterator<const N: usize> {
+pub struct PatternPuzzlesIterator<R: Rng, const N: usize> {
+    rng: R,
     starting_grid: Grid<N>,
     canonicalise: bool,
+    classic: bool,
     brute_force_solver: BruteForceSolver<N>,
     seed_stack: Vec<Puzzle>,
     iteration_queue: Vec<Puzzle>,
@@ -23,13 +25,15 @@ pub struct PatternPuzzlesIterator<const N: usize> {
     pattern: Pattern<N>,
 }
 
-impl<const N: usize> PatternPuzzlesIterator<N> {
+impl<R: Rng, const N: usize> PatternPuzzlesIterator<R, N> {
 
-    pub fn for_empty_grid_and_pattern(empty_grid: Grid<N>, pattern: Pattern<N>) -> Self {
+    pub fn for_empty_grid_and_pattern(empty_grid: Grid<N>, pattern: Pattern<N>, mut rng: R) -> Self {
         loop {
-            if let Some(puzzle) = PatternPuzzlesIterator::random_seed(&empty_grid, &pattern) {
+            if let Some(puzzle) = Self::random_seed(&mut rng, &empty_grid, &pattern) {
                 let brute_force_solver = BruteForceSolver::for_empty_grid(&empty_grid);
                 return Self {
+                    rng,
+                    classic: is_classic(&empty_grid),
                     starting_grid: empty_grid,
                     canonicalise: true,
                     brute_force_solver,
@@ -42,13 +46,15 @@ impl<const N: usize> PatternPuzzlesIterator<N> {
         }
     }
 
-    pub fn for_starting_grid_and_pattern(starting_grid: Grid<N>, pattern: Pattern<N>) -> Self {
+    pub fn for_starting_grid_and_pattern(starting_grid: Grid<N>, pattern: Pattern<N>, mut rng: R) -> Self {
         loop {
-            if let Some(puzzle) = PatternPuzzlesIterator::random_seed(&starting_grid, &pattern) {
+            if let Some(puzzle) = Self::random_seed(&mut rng, &starting_grid, &pattern) {
                 let brute_force_solver = BruteForceSolver::for_starting_grid(&starting_grid);
                 return Self {
+                    rng,
                     starting_grid,
                     canonicalise: false,
+                    classic: false,
                     brute_force_solver,
                     seed_stack: vec![puzzle],
                     iteration_queue: vec![],
@@ -59,12 +65,12 @@ impl<const N: usize> PatternPuzzlesIterator<N> {
         }
     }
 
-    fn random_seed(starting_grid: &Grid<N>, pattern: &Pattern<N>) -> Option<Puzzle> {
+    fn random_seed(rng: &mut R, starting_grid: &Grid<N>, pattern: &Pattern<N>) -> Option<Puzzle> {
         let mut puzzle = starting_grid.cells().iter().map(|c| starting_grid.value(c).map(|candidate| candidate.0).unwrap_or(0)).collect();
         for &cell in pattern {
-            let valid_clues = PatternPuzzlesIterator::valid_clues(starting_grid, &puzzle, cell);
+            let valid_clues = Self::valid_clues(starting_grid, &puzzle, cell);
             if valid_clues.is_empty() { return None; }
-            else { puzzle[cell.0] = *valid_clues.choose(&mut thread_rng()).unwrap(); }
+            else { puzzle[cell.0] = *valid_clues.choose(rng).unwrap(); }
         }
         Some(puzzle)
     }
@@ -84,7 +90,7 @@ impl<const N: usize> PatternPuzzlesIterator<N> {
     }
 }
 
-impl<const N: usize> Iterator for PatternPuzzlesIterator<N> {
+impl<R: Rng, const N: usize> Iterator for PatternPuzzlesIterator<R, N> {
     type Item = Puzzle;
 
     fn next(&mut self) -> Option<Puzzle> {
@@ -97,7 +103,7 @@ impl<const N: usize> Iterator for PatternPuzzlesIterator<N> {
         loop {
 
             while self.seed_stack.is_empty() {
-                if let Some(seed) = Self::random_seed(&self.starting_grid, &self.pattern) {
+                if let Some(seed) = Self::random_seed(&mut self.rng, &self.starting_grid, &self.pattern) {
                     self.seed_stack.push(seed);
                 }
             }
@@ -113,18 +119,44 @@ impl<const N: usize> Iterator for PatternPuzzlesIterator<N> {
                 for &c1 in &poss1 { puzzle[clue1.0] = c1;
                     for &c2 in &poss2 { puzzle[clue2.0] = c2;
                         let canonical_puzzle = if self.canonicalise { minlex::<N>(&puzzle) } else { puzzle.clone() };
-                        if !self.seen_puzzles.contains(&canonical_puzzle) && self.brute_force_solver.has_unique_solution(&canonical_puzzle) {
-                            self.seen_puzzles.insert(canonical_puzzle.clone());
+                        let key = if self.classic { classic_minlex(&canonical_puzzle) } else { canonical_puzzle.clone() };
+                        if !self.seen_puzzles.contains(&key) && self.brute_force_solver.has_unique_solution(&canonical_puzzle) {
+                            self.seen_puzzles.insert(key);
                             next_puzzles.push(canonical_puzzle);
                         }
                     }
                 }
             }
 
-            next_puzzles.shuffle(&mut thread_rng());
+            next_puzzles.shuffle(&mut self.rng);
             self.iteration_queue.append(&mut next_puzzles);
 
             if let Some(puzzle) = self.iteration_queue.pop() { return Some(puzzle); }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use crate::grid::Grid;
+    use crate::grid::cell::CellIdx;
+    use crate::grid::variants::Classic;
+
+    use super::PatternPuzzlesIterator;
+
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let puzzle = "6...7...2..23.97...9.2.4.8..35...87.4.......1.26...45..6.8.2.4...97.51..2...3...8";
+        let pattern: Vec<_> = puzzle.bytes().enumerate().filter(|&(_, b)| b != b'.').map(|(idx, _)| CellIdx::<9>(idx)).take(6).collect();
+        let starting_clues: String = puzzle.bytes().enumerate().map(|(idx, b)| if pattern.contains(&CellIdx(idx)) { '.' } else { b as char }).collect();
+        let starting_grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), &starting_clues).unwrap();
+
+        let first_run: Vec<_> = PatternPuzzlesIterator::for_starting_grid_and_pattern(starting_grid.clone(), pattern.clone(), StdRng::seed_from_u64(1234)).take(10).collect();
+        let second_run: Vec<_> = PatternPuzzlesIterator::for_starting_grid_and_pattern(starting_grid, pattern, StdRng::seed_from_u64(1234)).take(10).collect();
+        assert_eq!(first_run, second_run);
+    }
+}
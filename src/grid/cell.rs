@@ -26,12 +26,13 @@ impl<const N: usize> CellIdx<N> {
 pub struct Cell<const N: usize> {
     value: Option<Candidate<N>>,
     candidates: CandidateSet<N>,
+    given: bool,
 }
 
 impl<const N: usize> Cell<N> {
 
     pub fn empty() -> Self {
-        Self { value: None, candidates: CandidateSet::full() }
+        Self { value: None, candidates: CandidateSet::full(), given: false }
     }
 
     pub fn value(&self) -> Option<Candidate<N>> {
@@ -43,6 +44,10 @@ impl<const N: usize> Cell<N> {
         self.candidates = CandidateSet::empty();
     }
 
+    pub fn set_given(&mut self) {
+        self.given = true;
+    }
+
     pub fn eliminate_candidate(&mut self, value: Candidate<N>) {
         self.candidates.remove_value(value);
     }
@@ -51,6 +56,10 @@ impl<const N: usize> Cell<N> {
         self.value.is_none()
     }
 
+    pub fn is_given(&self) -> bool {
+        self.given
+    }
+
     pub fn candidates(&self) -> &CandidateSet<N> {
         &self.candidates
     }
@@ -70,6 +79,11 @@ impl<const N: usize> Cell<N> {
     pub fn has_any_of_candidates(&self, values: &CandidateSet<N>) -> bool {
         self.candidates.intersects(values)
     }
+
+    pub fn relabelled(&self, digits: &[Candidate<N>]) -> Self {
+        let relabel = |Candidate(value): Candidate<N>| digits[value - 1];
+        Self { value: self.value.map(relabel), candidates: CandidateSet::from_candidates(self.candidates.iter().map(relabel)), given: self.given }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -46,6 +46,10 @@ impl <const N: usize> Grid<N> {
         &self.regions
     }
 
+    pub fn houses(&self, house_type: House) -> &[CellSet<N>] {
+        match house_type { House::Row => &self.rows, House::Column => &self.cols, House::Region => &self.regions }
+    }
+
     pub fn all_houses(&self) -> &[CellSet<N>] {
         &self.all_houses
     }
@@ -62,6 +66,14 @@ impl <const N: usize> Grid<N> {
         CellSet::intersection(cells.iter().map(|cell| self.neighbours(cell)))
     }
 
+    pub fn seen_by(&self, cells: &CellSet<N>) -> CellSet<N> {
+        CellSet::union(cells.iter().map(|cell| self.neighbours(cell)))
+    }
+
+    pub fn sees_any(&self, cells1: &CellSet<N>, cells2: &CellSet<N>) -> bool {
+        self.seen_by(cells1).intersects(cells2)
+    }
+
     pub fn rows_with_candidate(&self, value: Candidate<N>) -> Vec<&CellSet<N>> {
         self.rows.iter().filter(|row| self.candidate_appears_in(row, value)).collect()
     }
@@ -91,7 +103,6 @@ impl <const N: usize> Grid<N> {
     }
 
     pub fn group_by(&self, cells: &CellSet<N>, house_type: House) -> Vec<CellSet<N>> {
-        let regions = match house_type { House::Row => &self.rows, House::Column => &self.cols, House::Region => &self.regions };
-        regions.iter().map(|region| region & cells).filter(|group| !group.is_empty()).collect()
+        self.houses(house_type).iter().map(|region| region & cells).filter(|group| !group.is_empty()).collect()
     }
 }
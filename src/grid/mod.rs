@@ -8,6 +8,7 @@ use std::fmt;
 pub mod candidate;
 pub mod cell;
 mod geometry;
+pub mod transforms;
 pub mod variants;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -25,6 +26,7 @@ pub struct Grid<const N: usize> {
     regions: Vec<CellSet<N>>,
     all_houses: Vec<CellSet<N>>,
     neighbours: Vec<CellSet<N>>,
+    unique_solution: bool,
 }
 
 impl<const N: usize> fmt::Display for Grid<N> {
@@ -56,7 +58,15 @@ impl<const N: usize> Grid<N> {
         let all_houses: Vec<_> = regions.iter().chain(rows.iter()).chain(cols.iter()).map(|cs| cs.clone()).collect();
         let neighbours = Self::create_neighbours(&all_houses, additional_neighbours);
 
-        Self { cells, rows, cols, regions, all_houses, neighbours }
+        Self { cells, rows, cols, regions, all_houses, neighbours, unique_solution: false }
+    }
+
+    pub fn declare_unique_solution(&mut self) {
+        self.unique_solution = true;
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.unique_solution
     }
 
     pub fn apply_deduction(&mut self, deduction: Deduction<N>) {
@@ -74,6 +84,11 @@ impl<const N: usize> Grid<N> {
         }
     }
 
+    pub fn place_given(&mut self, cell: CellIdx<N>, value: Candidate<N>) {
+        self.place_value(cell, value);
+        self.cells[cell.0].set_given();
+    }
+
     pub fn eliminate_candidate(&mut self, cell: CellIdx<N>, value: Candidate<N>) {
         self.cells[cell.0].eliminate_candidate(value);
     }
@@ -86,6 +101,10 @@ impl<const N: usize> Grid<N> {
         self.cells[cell.0].is_empty()
     }
 
+    pub fn is_given(&self, cell: CellIdx<N>) -> bool {
+        self.cells[cell.0].is_given()
+    }
+
     pub fn value(&self, cell: CellIdx<N>) -> Option<Candidate<N>> {
         self.cells[cell.0].value()
     }
@@ -114,6 +133,10 @@ impl<const N: usize> Grid<N> {
         self.empty_cells_in(&CellSet::full())
     }
 
+    pub fn given_cells(&self) -> CellSet<N> {
+        self.cells().filter(|&cell| self.is_given(cell))
+    }
+
     pub fn cells_with_candidate(&self, value: Candidate<N>) -> CellSet<N> {
         self.cells_with_candidate_in(&CellSet::full(), value)
     }
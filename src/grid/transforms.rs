@@ -0,0 +1,235 @@
+use super::candidate::Candidate;
+use super::Grid;
+use super::cell::{CellIdx, CellSet};
+
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+pub enum GridTransformError {
+    InvalidPermutation,
+    IncompatibleLayout,
+}
+
+impl fmt::Display for GridTransformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridTransformError::*;
+        match *self {
+            InvalidPermutation => write!(f, "The given ordering is not a valid permutation"),
+            IncompatibleLayout => write!(f, "The transform does not preserve the layout of the grid"),
+        }
+    }
+}
+
+impl fmt::Debug for GridTransformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+pub fn rotated_cell<const N: usize>(cell: CellIdx<N>, quarter_turns: usize) -> CellIdx<N> {
+    let (mut row, mut col) = (cell.row(), cell.col());
+    for _ in 0 .. quarter_turns % 4 { (row, col) = (col, N - 1 - row); }
+    CellIdx::from_row_and_col(row, col)
+}
+
+pub fn reflected_cell<const N: usize>(cell: CellIdx<N>, axis: Axis) -> CellIdx<N> {
+    let (row, col) = (cell.row(), cell.col());
+    match axis {
+        Axis::Horizontal => CellIdx::from_row_and_col(N - 1 - row, col),
+        Axis::Vertical => CellIdx::from_row_and_col(row, N - 1 - col),
+        Axis::Diagonal => CellIdx::from_row_and_col(col, row),
+        Axis::AntiDiagonal => CellIdx::from_row_and_col(N - 1 - col, N - 1 - row),
+    }
+}
+
+impl<const N: usize> Grid<N> {
+
+    pub fn rotate(&self, quarter_turns: usize) -> Result<Grid<N>, GridTransformError> {
+        self.transform_cells(|cell| rotated_cell(cell, quarter_turns))
+    }
+
+    pub fn reflect(&self, axis: Axis) -> Result<Grid<N>, GridTransformError> {
+        self.transform_cells(|cell| reflected_cell(cell, axis))
+    }
+
+    pub fn transpose(&self) -> Result<Grid<N>, GridTransformError> {
+        self.reflect(Axis::Diagonal)
+    }
+
+    pub fn permute_rows(&self, order: &[usize]) -> Result<Grid<N>, GridTransformError> {
+        let positions = Self::inverse_permutation(order, N)?;
+        self.transform_cells(|cell| CellIdx::from_row_and_col(positions[cell.row()], cell.col()))
+    }
+
+    pub fn permute_columns(&self, order: &[usize]) -> Result<Grid<N>, GridTransformError> {
+        let positions = Self::inverse_permutation(order, N)?;
+        self.transform_cells(|cell| CellIdx::from_row_and_col(cell.row(), positions[cell.col()]))
+    }
+
+    pub fn permute_bands(&self, order: &[usize]) -> Result<Grid<N>, GridTransformError> {
+        self.permute_rows(&Self::expand_block_order(order)?)
+    }
+
+    pub fn permute_stacks(&self, order: &[usize]) -> Result<Grid<N>, GridTransformError> {
+        self.permute_columns(&Self::expand_block_order(order)?)
+    }
+
+    pub fn relabel(&self, digits: &[Candidate<N>]) -> Result<Grid<N>, GridTransformError> {
+        let order: Vec<_> = digits.iter().map(|Candidate(value)| value.wrapping_sub(1)).collect();
+        Self::inverse_permutation(&order, N)?;
+
+        let mut grid = self.clone();
+        grid.cells = self.cells.iter().map(|cell| cell.relabelled(digits)).collect();
+        Ok(grid)
+    }
+
+    pub fn preserves_layout<F: Fn(CellIdx<N>) -> CellIdx<N>>(&self, mapping: F) -> bool {
+        let map_set = |cells: &CellSet<N>| CellSet::from_cells(cells.iter().map(&mapping));
+        self.regions.iter().all(|region| self.regions.contains(&map_set(region)))
+            && self.cells().iter().all(|cell| self.neighbours(mapping(cell)) == &map_set(self.neighbours(cell)))
+    }
+
+    fn transform_cells<F: Fn(CellIdx<N>) -> CellIdx<N>>(&self, mapping: F) -> Result<Grid<N>, GridTransformError> {
+        if !self.preserves_layout(&mapping) {
+            return Err(GridTransformError::IncompatibleLayout);
+        }
+
+        let mut grid = self.clone();
+        for cell in self.cells().iter() {
+            grid.cells[mapping(cell).0] = self.cells[cell.0].clone();
+        }
+        Ok(grid)
+    }
+
+    fn expand_block_order(order: &[usize]) -> Result<Vec<usize>, GridTransformError> {
+        if order.is_empty() || !N.is_multiple_of(order.len()) {
+            return Err(GridTransformError::InvalidPermutation);
+        }
+        Self::inverse_permutation(order, order.len())?;
+
+        let block_size = N / order.len();
+        Ok(order.iter().flat_map(|&block| block * block_size .. (block + 1) * block_size).collect())
+    }
+
+    fn inverse_permutation(order: &[usize], len: usize) -> Result<Vec<usize>, GridTransformError> {
+        let mut positions = vec![usize::MAX; len];
+        if order.len() != len {
+            return Err(GridTransformError::InvalidPermutation);
+        }
+        for (position, &idx) in order.iter().enumerate() {
+            if idx >= len || positions[idx] != usize::MAX {
+                return Err(GridTransformError::InvalidPermutation);
+            }
+            positions[idx] = position;
+        }
+        Ok(positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::cell::{CellIdx, CellSet};
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{all_strategies, Deduction, Step, SINGLES};
+
+    use super::{reflected_cell, rotated_cell, Axis, GridTransformError};
+
+    fn disguise(grid: &Grid<9>) -> Result<Grid<9>, GridTransformError> {
+        grid.rotate(1)?
+            .reflect(Axis::AntiDiagonal)?
+            .permute_bands(&[2, 0, 1])?
+            .permute_stacks(&[1, 2, 0])?
+            .permute_rows(&[1, 0, 2, 3, 5, 4, 8, 6, 7])?
+            .permute_columns(&[0, 2, 1, 5, 4, 3, 6, 7, 8])?
+            .relabel(&[4, 9, 1, 3, 7, 8, 2, 6, 5].map(Candidate))
+    }
+
+    fn disguise_deduction(deduction: Deduction<9>) -> Deduction<9> {
+        let digits = [4, 9, 1, 3, 7, 8, 2, 6, 5];
+        let position = |order: &[usize], line: usize| order.iter().position(|&idx| idx == line).unwrap();
+        let disguise_cell = |cell: CellIdx<9>| {
+            let cell = reflected_cell(rotated_cell(cell, 1), Axis::AntiDiagonal);
+            let (row, col) = (cell.row(), cell.col());
+            let (row, col) = (position(&[6, 7, 8, 0, 1, 2, 3, 4, 5], row), position(&[3, 4, 5, 6, 7, 8, 0, 1, 2], col));
+            CellIdx::from_row_and_col(position(&[1, 0, 2, 3, 5, 4, 8, 6, 7], row), position(&[0, 2, 1, 5, 4, 3, 6, 7, 8], col))
+        };
+        match deduction {
+            Deduction::Placement(cell, Candidate(value)) => Deduction::Placement(disguise_cell(cell), Candidate(digits[value - 1])),
+            Deduction::Elimination(cell, Candidate(value)) => Deduction::Elimination(disguise_cell(cell), Candidate(digits[value - 1])),
+            Deduction::Contradiction => Deduction::Contradiction,
+        }
+    }
+
+    fn first_step(grid: &Grid<9>) -> (Step<9>, Vec<Deduction<9>>) {
+        all_strategies(9).into_iter()
+            .flat_map(|strategy| strategy.find_steps(grid))
+            .map(|step| { let deductions = step.deductions(grid); (step, deductions) })
+            .find(|(_, deductions)| !deductions.is_empty())
+            .expect("No step found")
+    }
+
+    #[test]
+    fn test_solver_agrees_on_isomorphic_grids() {
+        let puzzles = [
+            (
+                "8.15......6....34....1.9...1...5......2....96.467.8..59.4....27...47...........6.",
+                "Y-Wing; pivot r8c8 and pincers (r8c3, r9c9) eliminate 3 from common neighbours",
+                "Y-Wing; pivot r1c8 and pincers (r1c6, r3c7) eliminate 1 from common neighbours",
+            ),
+            (
+                "..61..49.......8.37......2..7..1...6.....824..6.7.3.......2.9...28......54.6..1..",
+                "X-Wing; on value 9 with base (Row 4, Row 9), cover (Column 3, Column 6)",
+                "X-Wing; on value 5 with base (Row 3, Row 8), cover (Column 1, Column 6)",
+            ),
+            (
+                ".628....4..4.5..91.........5...42........91.2...1...8.8..4...2.9....6...2.7...6..",
+                "Swordfish; on value 7 with base (Column 4, Column 5, Column 8), cover (Row 3, Row 4, Row 8)",
+                "Swordfish; on value 2 with base (Column 2, Column 3, Column 8), cover (Row 1, Row 5, Row 8)",
+            ),
+        ];
+        for (puzzle, description, disguised_description) in puzzles {
+            let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+            let mut disguised_grid = disguise(&grid).expect("Failed to transform grid");
+            solve(&mut grid, SINGLES);
+            solve(&mut disguised_grid, SINGLES);
+
+            let (step, deductions) = first_step(&grid);
+            let (disguised_step, disguised_deductions) = first_step(&disguised_grid);
+            assert_eq!(step.description(&grid), description);
+            assert_eq!(disguised_step.description(&disguised_grid), disguised_description);
+
+            let mut expected: Vec<_> = deductions.into_iter().map(|deduction| disguise_deduction(deduction).description(&disguised_grid)).collect();
+            let mut actual: Vec<_> = disguised_deductions.iter().map(|deduction| deduction.description(&disguised_grid)).collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_jigsaw_rejects_incompatible_transforms() {
+        let mut regions = Grid::<9>::classic_regions();
+        let (cell1, cell2) = (CellIdx::from_row_and_col(0, 2), CellIdx::from_row_and_col(0, 3));
+        regions[0].remove_cell(cell1); regions[0].add_cell(cell2);
+        regions[1].remove_cell(cell2); regions[1].add_cell(cell1);
+        let jigsaw = Grid::<9>::empty(regions, vec![CellSet::empty(); 81]);
+
+        assert!(matches!(jigsaw.rotate(1), Err(GridTransformError::IncompatibleLayout)));
+        assert!(matches!(jigsaw.transpose(), Err(GridTransformError::IncompatibleLayout)));
+        assert!(matches!(jigsaw.permute_rows(&[1, 0, 2, 3, 4, 5, 6, 7, 8]), Err(GridTransformError::IncompatibleLayout)));
+        assert!(matches!(jigsaw.permute_rows(&[0, 0, 2, 3, 4, 5, 6, 7, 8]), Err(GridTransformError::InvalidPermutation)));
+        assert!(jigsaw.permute_rows(&[0, 1, 2, 3, 4, 5, 8, 7, 6]).is_ok());
+        assert!(jigsaw.relabel(&[2, 1, 3, 4, 5, 6, 7, 8, 9].map(Candidate)).is_ok());
+    }
+}
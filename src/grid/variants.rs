@@ -61,15 +61,29 @@ impl Mapper<9> for Grid<9> {
 
 impl<const N: usize> Grid<N> where Grid<N>: Mapper<N> {
 
-    pub fn from_empty_grid_and_clues(mut empty_grid: Grid<N>, clues: &[Option<Candidate<N>>]) -> Result<Grid<N>, GridParseError<N>> {
-        if clues.len() != N * N {
+    pub fn from_empty_grid_and_clues(empty_grid: Grid<N>, clues: &[Option<Candidate<N>>]) -> Result<Grid<N>, GridParseError<N>> {
+        Self::from_empty_grid_clues_and_placements(empty_grid, clues, &vec![None; N * N])
+    }
+
+    pub fn from_empty_grid_clues_and_placements(mut empty_grid: Grid<N>, clues: &[Option<Candidate<N>>], placements: &[Option<Candidate<N>>]) -> Result<Grid<N>, GridParseError<N>> {
+        if clues.len() != N * N || placements.len() != N * N {
             return Err(GridParseError::BadLength);
         }
 
         for (idx, clue) in clues.iter().enumerate() {
             if clue.is_some() {
                 if empty_grid.has_candidate(CellIdx(idx), clue.unwrap()) {
-                    empty_grid.place_value(CellIdx(idx), clue.unwrap());
+                    empty_grid.place_given(CellIdx(idx), clue.unwrap());
+                } else {
+                    return Err(GridParseError::Contradiction(CellIdx(idx)));
+                }
+            }
+        }
+
+        for (idx, placement) in placements.iter().enumerate() {
+            if placement.is_some() {
+                if empty_grid.has_candidate(CellIdx(idx), placement.unwrap()) {
+                    empty_grid.place_value(CellIdx(idx), placement.unwrap());
                 } else {
                     return Err(GridParseError::Contradiction(CellIdx(idx)));
                 }
@@ -83,4 +97,45 @@ impl<const N: usize> Grid<N> where Grid<N>: Mapper<N> {
         let clues: Vec<_> = input.bytes().map(Self::map_byte_to_candidate).collect();
         Self::from_empty_grid_and_clues(empty_grid, &clues)
     }
+
+    pub fn from_empty_grid_and_strings(empty_grid: Grid<N>, clues: &str, placements: &str) -> Result<Grid<N>, GridParseError<N>> {
+        let clues: Vec<_> = clues.bytes().map(Self::map_byte_to_candidate).collect();
+        let placements: Vec<_> = placements.bytes().map(Self::map_byte_to_candidate).collect();
+        Self::from_empty_grid_clues_and_placements(empty_grid, &clues, &placements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::cell::CellIdx;
+
+    use super::{Classic, GridParseError};
+
+    #[test]
+    fn test_clues_parse_as_givens_and_placements_as_solved() {
+        let grid = Grid::<9>::from_empty_grid_and_strings(
+            Grid::empty_classic(),
+            "1.......................................................................9........",
+            ".2.......................................................................5.......",
+        ).expect("Failed to parse grid");
+
+        let (clue, placement) = (CellIdx::from_row_and_col(0, 0), CellIdx::from_row_and_col(0, 1));
+        assert_eq!(grid.value(clue), Some(Candidate(1)));
+        assert!(grid.is_given(clue));
+        assert_eq!(grid.value(placement), Some(Candidate(2)));
+        assert!(!grid.is_given(placement));
+        assert_eq!(grid.cell_set_name(&grid.given_cells()), "(r1c1, r9c1)");
+        assert_eq!(grid.empty_cells().len(), 77);
+        assert!(!grid.has_candidate(CellIdx::from_row_and_col(0, 8), Candidate(2)));
+
+        let clashing = Grid::<9>::from_empty_grid_and_strings(
+            Grid::empty_classic(),
+            "1................................................................................",
+            "........1........................................................................",
+        );
+        assert!(matches!(clashing, Err(GridParseError::Contradiction(CellIdx(8)))));
+    }
 }
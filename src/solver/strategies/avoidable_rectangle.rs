@@ -0,0 +1,118 @@
+use crate::grid::Grid;
+use crate::grid::candidate::{Candidate, CandidateSet};
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::unique_rectangle::{is_deadly_rectangle, rectangles};
+use super::{AvoidableRectangleFlavour, Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    rectangles::<N>()
+        .filter(move |_| grid.has_unique_solution())
+        .filter(move |corners| grid.empty_cells_in(corners).len() < 3 && !corners.iter().any(|cell| grid.is_given(cell)))
+        .filter(move |corners| is_deadly_rectangle(grid, corners))
+        .flat_map(move |corners| find_for_rectangle(grid, &corners))
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, avoidable_rectangle: &Step<N>) -> Vec<Deduction<N>> {
+    match avoidable_rectangle {
+        Step::AvoidableRectangle { flavour, cells, value, .. } => {
+            let elimination_cells = match flavour {
+                AvoidableRectangleFlavour::Type1 => cells.clone(),
+                AvoidableRectangleFlavour::Type2 => grid.common_neighbours(cells),
+            };
+            grid.cells_with_candidate_in(&elimination_cells, *value).iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, avoidable_rectangle: &Step<N>) -> String {
+    match avoidable_rectangle {
+        Step::AvoidableRectangle { flavour, corners, cells, value } => match flavour {
+            AvoidableRectangleFlavour::Type1 => format!(
+                "Avoidable Rectangle Type 1; {} in {} would complete a deadly pattern with the solved cells of {}",
+                value.0, grid.cell_set_name(cells), grid.cell_set_name(corners)
+            ),
+            AvoidableRectangleFlavour::Type2 => format!(
+                "Avoidable Rectangle Type 2; {} must contain {} to avoid a deadly pattern in {}",
+                grid.cell_set_name(cells), value.0, grid.cell_set_name(corners)
+            ),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn find_for_rectangle<const N: usize>(grid: &Grid<N>, corners: &CellSet<N>) -> Vec<Step<N>> {
+    let corner_list: Vec<_> = corners.iter().collect();
+    let unsolved = grid.empty_cells_in(corners);
+    let step = |flavour, cells, value| Step::AvoidableRectangle { flavour, corners: corners.clone(), cells, value };
+
+    let deadly_value = |idx: usize| grid.value(corner_list[idx ^ 3]);
+
+    match unsolved.len() {
+        1 => {
+            let idx = corner_list.iter().position(|&cell| grid.is_empty(cell)).unwrap();
+            let (Some(x), Some(y1), Some(y2)) = (deadly_value(idx), grid.value(corner_list[idx ^ 1]), grid.value(corner_list[idx ^ 2])) else { return vec![] };
+            if y1 == y2 && x != y1 && grid.has_candidate(corner_list[idx], x) {
+                vec![step(AvoidableRectangleFlavour::Type1, unsolved, x)]
+            } else {
+                vec![]
+            }
+        },
+        2 if grid.all_houses().iter().any(|house| house.contains_all(&unsolved)) => {
+            let cells: Vec<_> = (0 .. 4).filter(|&idx| grid.is_empty(corner_list[idx])).collect();
+            let (Some(x), Some(y)) = (deadly_value(cells[0]), deadly_value(cells[1])) else { return vec![] };
+            let extras: Vec<_> = cells.iter().zip([x, y])
+                .map(|(&idx, deadly)| extra_candidate(grid, corner_list[idx], deadly))
+                .collect();
+            match extras[..] {
+                [Some(z1), Some(z2)] if z1 == z2 && x != y => vec![step(AvoidableRectangleFlavour::Type2, unsolved, z1)],
+                _ => vec![],
+            }
+        },
+        _ => vec![],
+    }
+}
+
+fn extra_candidate<const N: usize>(grid: &Grid<N>, cell: CellIdx<N>, deadly_value: Candidate<N>) -> Option<Candidate<N>> {
+    let candidates = grid.candidates(cell);
+    if candidates.len() == 2 && candidates.contains(deadly_value) {
+        (candidates & !CandidateSet::from_candidates([deadly_value])).first()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::generator::brute_force::BruteForceSolver;
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::variants::Classic;
+    use crate::solver::strategies::{Deduction, Strategy, SINGLES};
+    use crate::solver::solve;
+
+    #[test]
+    fn test_avoidable_rectangle() {
+        let puzzle = ".9...6...1.4.............8.6573....9..1.5.8.....29..1.....2..9....8.317.7.......5";
+        let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+        let solution = BruteForceSolver::for_starting_grid(&grid).solution(&[0; 81]);
+        grid.declare_unique_solution();
+        solve(&mut grid, &[SINGLES, &[Strategy::PointingClaiming, Strategy::YWing, Strategy::UniqueRectangle]].concat());
+
+        let step = Strategy::AvoidableRectangle.find_steps(&grid).next().expect("No avoidable rectangle found");
+        assert_eq!(
+            step.description(&grid),
+            "Avoidable Rectangle Type 1; 9 in (r3c4) would complete a deadly pattern with the solved cells of (r3c4, r3c6, r9c4, r9c6)"
+        );
+        let deductions = step.deductions(&grid);
+        for &deduction in &deductions {
+            match deduction {
+                Deduction::Elimination(cell, value) => assert_ne!(value, Candidate(solution[cell.0])),
+                _ => panic!("Unexpected deduction"),
+            }
+        }
+        assert_eq!(deductions.iter().map(|deduction| deduction.description(&grid)).collect::<Vec<_>>(), ["9 eliminated from r3c4"]);
+    }
+}
@@ -0,0 +1,76 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellIdx;
+
+use super::{Deduction, Step};
+
+const MAX_EXTRA_CELLS: usize = 4;
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let extra_cells = grid.empty_cells() & !grid.cells_with_n_candidates(2);
+    let is_candidate = grid.has_unique_solution()
+        && !extra_cells.is_empty() && extra_cells.len() <= MAX_EXTRA_CELLS
+        && extra_cells.iter().all(|cell| grid.num_candidates(cell) > 2);
+
+    let cells: Vec<_> = if is_candidate { extra_cells.iter().collect() } else { vec![] };
+    let kept_pairs = cells.iter().map(|&cell| grid.candidates(cell).iter().combinations(2)).multi_cartesian_product();
+
+    kept_pairs
+        .filter(move |_| is_candidate)
+        .map(move |kept| cells.iter().zip(kept).flat_map(|(&cell, kept)| {
+            grid.candidates(cell).iter().filter(move |value| !kept.contains(value)).map(move |value| (cell, value))
+        }).collect::<Vec<_>>())
+        .filter(move |extras| is_bivalue_universal_grave(grid, extras))
+        .map(|extras| Step::Bug { extras })
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, bug: &Step<N>) -> Vec<Deduction<N>> {
+    match bug {
+        Step::Bug { extras } if extras.len() == 1 => vec![Deduction::Placement(extras[0].0, extras[0].1)],
+        Step::Bug { extras } => grid.empty_cells().iter()
+            .flat_map(|cell| grid.candidates(cell).into_iter().map(move |value| (cell, value)))
+            .filter(|&(cell, value)| extras.iter().all(|&(extra_cell, extra_value)| {
+                if extra_cell == cell { extra_value != value } else { extra_value == value && grid.neighbours(cell).contains(extra_cell) }
+            }))
+            .map(|(cell, value)| Deduction::Elimination(cell, value))
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, bug: &Step<N>) -> String {
+    match bug {
+        Step::Bug { extras } => format!(
+            "BUG+{}; without {} the grid would be a bivalue universal grave, so one of them must be true",
+            extras.len(), extras.iter().map(|&(cell, value)| format!("{} in {}", value.0, grid.cell_name(cell))).join(", ")
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn is_bivalue_universal_grave<const N: usize>(grid: &Grid<N>, extras: &[(CellIdx<N>, Candidate<N>)]) -> bool {
+    grid.all_houses().iter().all(|house| {
+        grid.all_values().iter().all(|value| {
+            let num_extras = extras.iter().filter(|&&(cell, extra_value)| extra_value == value && house.contains(cell)).count();
+            matches!(grid.cells_with_candidate_in(house, value).len() - num_extras, 0 | 2)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_bug() {
+        assert_deductions(Strategy::Bug, &[
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["1 placed in r3c8"]),
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["6 placed in r4c5"]),
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["1 placed in r6c5"]),
+        ]);
+    }
+}
@@ -14,6 +14,7 @@ struct AicInference<const N: usize> {
 }
 
 type AffectedCandidate<const N: usize> = (CellIdx<N>, Candidate<N>);
+pub type Conclusion<const N: usize> = (CellIdx<N>, Candidate<N>, bool);
 pub struct Aic<const N: usize> { chain: Vec<AicInference<N>>, pub is_loop: bool }
 
 impl<const N: usize> AicInference<N> {
@@ -78,29 +79,71 @@ fn find_affected_candidates<const N: usize>(grid: &Grid<N>, node: &ChainNode<N>)
     affected_candidates
 }
 
-struct AicSearcher<'a, const N: usize> {
+pub struct ImplicationSearcher<'a, const N: usize> {
     grid: &'a Grid<N>,
     nodes: Vec<ChainNode<N>>,
     adjacencies: Vec<Vec<usize>>,
     affected_candidates: Vec<HashSet<AffectedCandidate<N>>>,
-    queue: VecDeque<(usize, usize)>,
-    parents: Vec<Vec<usize>>,
 }
 
-impl<'a, const N: usize> AicSearcher<'a, N> {
+pub struct Implications<const N: usize> {
+    start_idx: usize,
+    parents: Vec<usize>,
+    visited: Vec<usize>,
+    pub eliminations: HashSet<AffectedCandidate<N>>,
+}
 
-    fn for_nodes(grid: &'a Grid<N>, nodes: Vec<ChainNode<N>>, xy_chain: bool) -> Self {
+impl<'a, const N: usize> ImplicationSearcher<'a, N> {
+
+    pub fn for_nodes(grid: &'a Grid<N>, nodes: Vec<ChainNode<N>>, xy_chain: bool) -> Self {
         let adjacencies = Self::create_adjacencies(grid, &nodes, xy_chain);
         let affected_candidates = nodes.iter().map(|node| find_affected_candidates(grid, node)).collect();
-        
-        let mut queue = VecDeque::new();
-        let parents = vec![vec![usize::MAX; adjacencies.len()]; nodes.len()];
+        Self { grid, nodes, adjacencies, affected_candidates }
+    }
 
-        for start_idx in 0 .. nodes.len() { 
-            queue.push_back((start_idx, 2 * start_idx + 1));
+    pub fn implications(&self, start_node: &ChainNode<N>, negated: bool) -> Option<Implications<N>> {
+        let start_idx = 2 * self.nodes.iter().position(|node| node == start_node)? + usize::from(negated);
+        let mut parents = vec![usize::MAX; self.adjacencies.len()];
+        let mut visited = vec![start_idx];
+        parents[start_idx] = start_idx;
+
+        let mut queue = VecDeque::from([start_idx]);
+        while let Some(current_idx) = queue.pop_front() {
+            for &next_idx in &self.adjacencies[current_idx] {
+                if parents[next_idx] == usize::MAX {
+                    parents[next_idx] = current_idx;
+                    visited.push(next_idx);
+                    queue.push_back(next_idx);
+                }
+            }
         }
 
-        Self { grid, nodes, adjacencies, affected_candidates, queue, parents }
+        let eliminations = visited.iter()
+            .filter(|&&idx| idx % 2 == 0)
+            .flat_map(|&idx| self.affected_candidates[idx / 2].iter().copied())
+            .collect();
+        Some(Implications { start_idx, parents, visited, eliminations })
+    }
+
+    pub fn chain_eliminating(&self, implications: &Implications<N>, candidate: AffectedCandidate<N>) -> Option<Aic<N>> {
+        implications.visited.iter()
+            .find(|&&idx| idx % 2 == 0 && self.affected_candidates[idx / 2].contains(&candidate))
+            .map(|&end_idx| Aic { chain: self.create_chain(&implications.parents, implications.start_idx, end_idx), is_loop: false })
+    }
+
+    pub fn conclusions(&self, implications: &Implications<N>) -> HashSet<Conclusion<N>> {
+        implications.visited.iter()
+            .filter_map(|&idx| match self.nodes[idx / 2] {
+                ChainNode::Value { cell, value } => Some((cell, value, idx % 2 == 1)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn chain_to(&self, implications: &Implications<N>, (cell, value, negated): Conclusion<N>) -> Option<Aic<N>> {
+        let end_idx = 2 * self.nodes.iter().position(|node| *node == ChainNode::Value { cell, value })? + usize::from(negated);
+        if implications.parents[end_idx] == usize::MAX { return None; }
+        Some(Aic { chain: self.create_chain(&implications.parents, implications.start_idx, end_idx), is_loop: false })
     }
 
     fn create_adjacencies(grid: &'a Grid<N>, nodes: &[ChainNode<N>], xy_chain: bool) -> Vec<Vec<usize>> {
@@ -120,16 +163,42 @@ impl<'a, const N: usize> AicSearcher<'a, N> {
         adjacencies
     }
 
-    fn create_chain(&self, start_idx: usize, end_idx: usize) -> Aic<N> {
+    fn create_chain(&self, parents: &[usize], start_idx: usize, end_idx: usize) -> Vec<AicInference<N>> {
         let mut chain = Vec::new(); let mut current_idx = end_idx;
         loop {
             chain.push(AicInference { node: self.nodes[current_idx / 2].clone(), negated: current_idx % 2 == 1 });
             if current_idx == start_idx { break; }
-            current_idx = self.parents[start_idx / 2][current_idx];
+            current_idx = parents[current_idx];
         }
         chain.reverse();
+        chain
+    }
+}
+
+struct AicSearcher<'a, const N: usize> {
+    searcher: ImplicationSearcher<'a, N>,
+    queue: VecDeque<(usize, usize)>,
+    parents: Vec<Vec<usize>>,
+}
+
+impl<'a, const N: usize> AicSearcher<'a, N> {
+
+    fn for_nodes(grid: &'a Grid<N>, nodes: Vec<ChainNode<N>>, xy_chain: bool) -> Self {
+        let searcher = ImplicationSearcher::for_nodes(grid, nodes, xy_chain);
+
+        let mut queue = VecDeque::new();
+        let parents = vec![vec![usize::MAX; searcher.adjacencies.len()]; searcher.nodes.len()];
+
+        for start_idx in 0 .. searcher.nodes.len() { 
+            queue.push_back((start_idx, 2 * start_idx + 1));
+        }
+
+        Self { searcher, queue, parents }
+    }
 
-        let is_loop = nodes::is_weakly_linked(self.grid, &self.nodes[end_idx / 2], &self.nodes[start_idx / 2]);
+    fn create_chain(&self, start_idx: usize, end_idx: usize) -> Aic<N> {
+        let chain = self.searcher.create_chain(&self.parents[start_idx / 2], start_idx, end_idx);
+        let is_loop = nodes::is_weakly_linked(self.searcher.grid, &self.searcher.nodes[end_idx / 2], &self.searcher.nodes[start_idx / 2]);
         Aic { chain, is_loop }
     }
 }
@@ -140,14 +209,14 @@ impl<'a, const N: usize> Iterator for AicSearcher<'a, N> {
     fn next(&mut self) -> Option<Self::Item> {
         while let Some((start_idx, current_idx)) = self.queue.pop_front() {
 
-            for &next_idx in &self.adjacencies[current_idx] {
+            for &next_idx in &self.searcher.adjacencies[current_idx] {
                 if self.parents[start_idx][next_idx] == usize::MAX {
                     self.queue.push_back((start_idx, next_idx));
                     self.parents[start_idx][next_idx] = current_idx;
                 }
             }
 
-            if current_idx % 2 == 0 && !self.affected_candidates[current_idx / 2].is_disjoint(&self.affected_candidates[start_idx]) {
+            if current_idx % 2 == 0 && !self.searcher.affected_candidates[current_idx / 2].is_disjoint(&self.searcher.affected_candidates[start_idx]) {
                 return Some(self.create_chain(2 * start_idx + 1, current_idx));
             }
         }
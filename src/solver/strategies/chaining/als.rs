@@ -0,0 +1,139 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::{Candidate, CandidateSet};
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::nodes::almost_locked_sets;
+use super::super::{Deduction, Step};
+
+type Als<const N: usize> = (CellSet<N>, CandidateSet<N>);
+
+pub fn find_als_xzs<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let sets = almost_locked_sets(grid);
+    let links = restricted_common_links(grid, &sets);
+    (0 .. sets.len())
+        .flat_map(move |idx1| {
+            let links_by_set = links[idx1].iter().filter(|&&(idx2, _)| idx2 > idx1).into_group_map_by(|&&(idx2, _)| idx2);
+            links_by_set.into_iter()
+                .map(|(idx2, links)| Step::AlsXz {
+                    als1: sets[idx1].0.clone(), als2: sets[idx2].0.clone(), restricted_commons: CandidateSet::from_candidates(links.into_iter().map(|&(_, value)| value)),
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn find_als_xy_wings<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let sets = almost_locked_sets(grid);
+    let links = restricted_common_links(grid, &sets);
+    (0 .. sets.len())
+        .flat_map(move |pivot| {
+            links[pivot].iter().tuple_combinations()
+                .filter(|((idx1, value1), (idx2, value2))| value1 != value2 && !sets[*idx1].0.intersects(&sets[*idx2].0))
+                .filter(|((idx1, value1), (idx2, value2))| {
+                    (&sets[*idx1].1 & &sets[*idx2].1).iter().any(|value| value != *value1 && value != *value2)
+                })
+                .map(|((idx1, value1), (idx2, value2))| Step::AlsXyWing {
+                    pivot: sets[pivot].0.clone(), als1: sets[*idx1].0.clone(), als2: sets[*idx2].0.clone(), restricted_commons: (*value1, *value2),
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, als_step: &Step<N>) -> Vec<Deduction<N>> {
+    match als_step {
+        Step::AlsXz { als1, als2, restricted_commons } => {
+            let (candidates1, candidates2) = (grid.candidates_in(als1), grid.candidates_in(als2));
+            let mut eliminations = Vec::new();
+            if restricted_commons.len() == 1 {
+                let common_values = &(&candidates1 & &candidates2) & !restricted_commons;
+                eliminations.extend(common_values.iter().flat_map(|value| eliminations_for_value(grid, &(als1 | als2), value)));
+            } else {
+                eliminations.extend(restricted_commons.iter().flat_map(|value| eliminations_for_value(grid, &(als1 | als2), value)));
+                for (als, candidates) in [(als1, &candidates1), (als2, &candidates2)] {
+                    let locked_values = candidates & !restricted_commons;
+                    eliminations.extend(locked_values.iter().flat_map(|value| eliminations_for_value(grid, als, value)));
+                }
+            }
+            eliminations.into_iter().unique().map(|(cell, value)| Deduction::Elimination(cell, value)).collect()
+        },
+        Step::AlsXyWing { als1, als2, restricted_commons: (value1, value2), .. } => {
+            let common_values = &grid.candidates_in(als1) & &grid.candidates_in(als2);
+            common_values.iter()
+                .filter(|value| value != value1 && value != value2)
+                .flat_map(|value| eliminations_for_value(grid, &(als1 | als2), value))
+                .map(|(cell, value)| Deduction::Elimination(cell, value))
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, als_step: &Step<N>) -> String {
+    match als_step {
+        Step::AlsXz { als1, als2, restricted_commons } => format!(
+            "ALS-XZ{}; {} and {} are linked by restricted common{} {}",
+            if restricted_commons.len() > 1 { " (Doubly Linked)" } else { "" },
+            grid.cell_set_name(als1), grid.cell_set_name(als2), if restricted_commons.len() > 1 { "s" } else { "" }, restricted_commons
+        ),
+        Step::AlsXyWing { pivot, als1, als2, restricted_commons: (value1, value2) } => format!(
+            "ALS-XY-Wing; pivot {} is linked to {} by restricted common {} and to {} by restricted common {}",
+            grid.cell_set_name(pivot), grid.cell_set_name(als1), value1.0, grid.cell_set_name(als2), value2.0
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn restricted_common_links<const N: usize>(grid: &Grid<N>, sets: &[Als<N>]) -> Vec<Vec<(usize, Candidate<N>)>> {
+    let value_cells: Vec<Vec<_>> = sets.iter()
+        .map(|(cells, _)| grid.all_values().iter().map(|value| grid.cells_with_candidate_in(cells, value)).collect())
+        .collect();
+    let seen_cells: Vec<Vec<_>> = value_cells.iter()
+        .map(|cells_by_value| cells_by_value.iter().map(|cells| grid.common_neighbours(cells)).collect())
+        .collect();
+
+    let mut links = vec![Vec::new(); sets.len()];
+    for ((idx1, (als1, candidates1)), (idx2, (als2, candidates2))) in sets.iter().enumerate().tuple_combinations() {
+        if !candidates1.intersects(candidates2) || als1.intersects(als2) { continue; }
+        for value in (candidates1 & candidates2).iter() {
+            if seen_cells[idx1][value.0 - 1].contains_all(&value_cells[idx2][value.0 - 1]) {
+                links[idx1].push((idx2, value));
+                links[idx2].push((idx1, value));
+            }
+        }
+    }
+    links
+}
+
+fn eliminations_for_value<const N: usize>(grid: &Grid<N>, cells: &CellSet<N>, value: Candidate<N>) -> Vec<(CellIdx<N>, Candidate<N>)> {
+    let value_cells = grid.cells_with_candidate_in(cells, value);
+    let elimination_cells = grid.cells_with_candidate_in(&grid.common_neighbours(&value_cells), value) & !cells;
+    elimination_cells.iter().map(|cell| (cell, value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_als_xz() {
+        assert_deductions(Strategy::AlsXz, &[
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["1 eliminated from r6c2"]),
+            ("....7.943..25..1..7......5.84...62.....9..7....54...6..58..4..1...6..........5..7", &["1 eliminated from r3c2"]),
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["1 eliminated from r3c7"]),
+        ]);
+    }
+
+    #[test]
+    fn test_als_xy_wing() {
+        assert_deductions(Strategy::AlsXyWing, &[
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["4 eliminated from r5c7"]),
+            ("....7.943..25..1..7......5.84...62.....9..7....54...6..58..4..1...6..........5..7", &["3 eliminated from r9c2"]),
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["1 eliminated from r3c7"]),
+        ]);
+    }
+}
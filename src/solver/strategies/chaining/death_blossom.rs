@@ -0,0 +1,90 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellSet;
+
+use super::nodes::almost_locked_sets;
+use super::super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let sets = almost_locked_sets(grid);
+    grid.empty_cells().into_iter()
+        .filter(move |&stem| grid.num_candidates(stem) > 1)
+        .flat_map(move |stem| {
+            let petal_values = grid.candidates(stem);
+            let sets: Vec<_> = sets.iter().filter(|(cells, _)| !cells.contains(stem)).collect();
+            (grid.all_values() & !petal_values).iter()
+                .flat_map(|value| {
+                    let petals_by_value: Vec<Vec<_>> = petal_values.iter()
+                        .map(|petal_value| sets.iter()
+                            .filter(|(cells, candidates)| {
+                                candidates.contains(petal_value) && candidates.contains(value)
+                                    && grid.neighbours(stem).contains_all(&grid.cells_with_candidate_in(cells, petal_value))
+                            })
+                            .map(|(cells, _)| cells)
+                            .collect())
+                        .collect();
+                    let targets = grid.cells_with_candidate_in(&grid.empty_cells(), value);
+                    blossoms(grid, &petals_by_value, value, targets, &CellSet::empty()).into_iter()
+                        .map(|petals| Step::DeathBlossom {
+                            stem, petals: petal_values.iter().zip(petals).map(|(petal_value, petal)| (petal_value, petal.clone())).collect(), value,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, death_blossom: &Step<N>) -> Vec<Deduction<N>> {
+    match death_blossom {
+        Step::DeathBlossom { petals, value, .. } => {
+            let petal_cells = CellSet::union(petals.iter().map(|(_, cells)| cells.clone()));
+            let value_cells = grid.cells_with_candidate_in(&petal_cells, *value);
+            let elimination_cells = grid.cells_with_candidate_in(&grid.common_neighbours(&value_cells), *value) & !petal_cells;
+            elimination_cells.iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, death_blossom: &Step<N>) -> String {
+    match death_blossom {
+        Step::DeathBlossom { stem, petals, value } => format!(
+            "Death Blossom; stem {} has petals {}, so one of the petals must contain {}",
+            grid.cell_name(*stem), petals.iter().map(|(petal_value, cells)| format!("{} ({})", grid.cell_set_name(cells), petal_value.0)).join(", "), value.0
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn blossoms<'a, const N: usize>(grid: &Grid<N>, petals_by_value: &[Vec<&'a CellSet<N>>], value: Candidate<N>, targets: CellSet<N>, used: &CellSet<N>) -> Vec<Vec<&'a CellSet<N>>> {
+    let Some((petals, rest)) = petals_by_value.split_first() else { return vec![vec![]] };
+    petals.iter()
+        .flat_map(|&petal| {
+            let used = used | petal;
+            let targets = &(&targets & &grid.common_neighbours(&grid.cells_with_candidate_in(petal, value))) & !&used;
+            if targets.is_empty() { return vec![]; }
+            blossoms(grid, rest, value, targets, &used).into_iter()
+                .map(|mut blossom| { blossom.insert(0, petal); blossom })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_death_blossom() {
+        assert_deductions(Strategy::DeathBlossom, &[
+            ("....7.943..25..1..7......5.84...62.....9..7....54...6..58..4..1...6..........5..7", &["3 eliminated from r9c2"]),
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["4 eliminated from r4c2"]),
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["1 eliminated from r3c9"]),
+        ]);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::aic::Conclusion;
+use super::forcing::{branch_tree, cell_premises, digit_premises, premise_description, premise_name, unit_premises};
+use super::super::{Deduction, ForcingPremise, Step};
+
+pub struct DynamicChain<const N: usize> { literals: Vec<Conclusion<N>>, contradiction: bool }
+
+struct Propagation<const N: usize> {
+    order: Vec<Conclusion<N>>,
+    parents: HashMap<Conclusion<N>, Vec<Conclusion<N>>>,
+    contradiction: Option<Vec<Conclusion<N>>>,
+}
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>, level: usize) -> impl Iterator<Item = Step<N>> + 'a {
+    let mut cache = HashMap::new();
+    digit_premises(grid).chain(cell_premises(grid)).chain(unit_premises(grid))
+        .flat_map(move |(premise, assumptions)| find_for_premise(grid, level, &mut cache, premise, &assumptions))
+}
+
+pub fn deductions<const N: usize>(_grid: &Grid<N>, dynamic_chain: &Step<N>) -> Vec<Deduction<N>> {
+    match dynamic_chain {
+        Step::DynamicForcingChain { conclusion, .. } => vec![*conclusion],
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, dynamic_chain: &Step<N>) -> String {
+    match dynamic_chain {
+        Step::DynamicForcingChain { premise, conclusion, level, branches } => format!(
+            "Dynamic {} Forcing Chain (level {}); {} {}{}", premise_name(premise), level, premise_description(grid, premise), conclusion.description(grid),
+            branch_tree(branches.iter().map(|branch| chain_description(grid, branch)).collect())
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn chain_description<const N: usize>(grid: &Grid<N>, chain: &DynamicChain<N>) -> String {
+    let mut description = chain.literals.iter()
+        .map(|&(cell, value, negated)| format!("{}{}{}", if negated { "-" } else { "+" }, value.0, grid.cell_name(cell)))
+        .join(" --> ");
+    if chain.contradiction { description.push_str(" --> Contradiction"); }
+    description
+}
+
+fn find_for_premise<const N: usize>(grid: &Grid<N>, level: usize, cache: &mut HashMap<Conclusion<N>, Propagation<N>>, premise: ForcingPremise<N>, assumptions: &[Conclusion<N>]) -> Vec<Step<N>> {
+    for &assumption in assumptions {
+        cache.entry(assumption).or_insert_with(|| propagate(grid, assumption, level));
+    }
+    let propagations: Vec<_> = assumptions.iter().map(|assumption| &cache[assumption]).collect();
+
+    let conclusions = propagations.iter()
+        .filter(|propagation| propagation.contradiction.is_none())
+        .map(|propagation| propagation.parents.keys().copied().collect::<HashSet<_>>())
+        .reduce(|conclusions, branch_conclusions| &conclusions & &branch_conclusions)
+        .unwrap_or_default();
+
+    conclusions.into_iter()
+        .filter(|&(cell, value, _)| grid.has_candidate(cell, value) && grid.num_candidates(cell) > 1)
+        .sorted_by_key(|&(cell, value, negated)| (negated, cell.0, value.0))
+        .map(|conclusion| {
+            let branches = propagations.iter()
+                .map(|propagation| match &propagation.contradiction {
+                    Some(causes) => DynamicChain { literals: propagation.ancestors(causes), contradiction: true },
+                    None => DynamicChain { literals: propagation.ancestors(&[conclusion]), contradiction: false },
+                })
+                .collect();
+            let (cell, value, negated) = conclusion;
+            let conclusion = if negated { Deduction::Elimination(cell, value) } else { Deduction::Placement(cell, value) };
+            Step::DynamicForcingChain { premise: premise.clone(), conclusion, level, branches }
+        })
+        .collect()
+}
+
+fn propagate<const N: usize>(grid: &Grid<N>, start: Conclusion<N>, level: usize) -> Propagation<N> {
+    let mut propagation = Propagation { order: vec![], parents: HashMap::new(), contradiction: None };
+    let mut state = grid.clone();
+    let mut queue = VecDeque::new();
+    propagation.derive(start, vec![], &mut queue);
+
+    loop {
+        while let Some(literal) = queue.pop_front() {
+            if propagation.contradiction.is_some() { return propagation; }
+            propagation.apply(grid, &mut state, literal, &mut queue);
+        }
+        if propagation.contradiction.is_some() || level == 0 { return propagation; }
+
+        let Some(nested) = nested_elimination(&state, level - 1) else { return propagation };
+        let parents = propagation.order.clone();
+        propagation.derive(nested, parents, &mut queue);
+    }
+}
+
+fn nested_elimination<const N: usize>(state: &Grid<N>, level: usize) -> Option<Conclusion<N>> {
+    state.empty_cells().iter()
+        .flat_map(|cell| state.candidates(cell).iter().map(move |value| (cell, value)).collect::<Vec<_>>())
+        .find(|&(cell, value)| propagate(state, (cell, value, false), level).contradiction.is_some())
+        .map(|(cell, value)| (cell, value, true))
+}
+
+impl<const N: usize> Propagation<N> {
+
+    fn derive(&mut self, literal: Conclusion<N>, parents: Vec<Conclusion<N>>, queue: &mut VecDeque<Conclusion<N>>) {
+        if self.contradiction.is_some() || self.parents.contains_key(&literal) { return; }
+        self.parents.insert(literal, parents);
+        self.order.push(literal);
+
+        let (cell, value, negated) = literal;
+        if self.parents.contains_key(&(cell, value, !negated)) {
+            self.contradiction = Some(vec![(cell, value, !negated), literal]);
+        } else {
+            queue.push_back(literal);
+        }
+    }
+
+    fn apply(&mut self, grid: &Grid<N>, state: &mut Grid<N>, literal: Conclusion<N>, queue: &mut VecDeque<Conclusion<N>>) {
+        let (cell, value, negated) = literal;
+        if !negated {
+            if state.value(cell) == Some(value) { return; }
+            if !state.has_candidate(cell, value) { self.contradiction = Some(vec![literal]); return; }
+            for other in state.candidates(cell).iter().filter(|&other| other != value) {
+                self.derive((cell, other, true), vec![literal], queue);
+            }
+            for neighbour in state.cells_with_candidate_in(state.neighbours(cell), value).iter() {
+                self.derive((neighbour, value, true), vec![literal], queue);
+            }
+            state.place_value(cell, value);
+            return;
+        }
+
+        if state.value(cell) == Some(value) { self.contradiction = Some(vec![literal]); return; }
+        if state.value(cell).is_some() { return; }
+        state.eliminate_candidate(cell, value);
+
+        let cell_causes = self.derived_eliminations(grid.candidates(cell).iter().map(|other| (cell, other)));
+        match state.num_candidates(cell) {
+            0 => { self.contradiction = Some(cell_causes); return; },
+            1 => {
+                let remaining = state.candidates(cell).iter().next().unwrap();
+                self.derive((cell, remaining, false), cell_causes, queue);
+            },
+            _ => {},
+        }
+
+        for house in grid.all_houses_containing(&CellSet::from_cells([cell])) {
+            if house.iter().any(|other| state.value(other) == Some(value)) { continue; }
+            let positions = state.cells_with_candidate_in(&state.empty_cells_in(house), value);
+            let house_causes = self.derived_eliminations(grid.cells_with_candidate_in(house, value).iter().map(|other| (other, value)));
+            match positions.len() {
+                0 => { self.contradiction = Some(house_causes); return; },
+                1 => self.derive((positions.first().unwrap(), value, false), house_causes, queue),
+                _ => {},
+            }
+        }
+    }
+
+    fn derived_eliminations(&self, candidates: impl Iterator<Item = (CellIdx<N>, Candidate<N>)>) -> Vec<Conclusion<N>> {
+        candidates.map(|(cell, value)| (cell, value, true)).filter(|literal| self.parents.contains_key(literal)).collect()
+    }
+
+    fn ancestors(&self, targets: &[Conclusion<N>]) -> Vec<Conclusion<N>> {
+        let mut ancestors = HashSet::new();
+        let mut stack = targets.to_vec();
+        while let Some(literal) = stack.pop() {
+            if ancestors.insert(literal) {
+                stack.extend(self.parents.get(&literal).into_iter().flatten().copied());
+            }
+        }
+        self.order.iter().filter(|literal| ancestors.contains(literal)).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{ForcingPremise, Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    use super::super::forcing::cell_premises;
+    use super::find_for_premise;
+
+    #[test]
+    fn test_dynamic_forcing_chains() {
+        assert_deductions(Strategy::DynamicForcingChain(0), &[
+            ("..7...964..4.2..........5....17.63....89.........3.....52...8..64.8...12....9..5.", &["1 eliminated from r1c1"]),
+            ("....3...4.3.7...2...25...7...591.2.3.6.......9.1...8.....183..55.6.9.........4...", &["1 eliminated from r1c1"]),
+        ]);
+        assert_deductions(Strategy::DynamicForcingChain(1), &[
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["7 placed in r1c5"]),
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["1 placed in r3c5"]),
+        ]);
+    }
+
+    #[test]
+    fn test_contradicted_branches_are_left_out_of_the_conclusions() {
+        let mut grid = Grid::<9>::from_empty_grid_and_string(
+            Grid::empty_classic(), "..9..18..6...7.3..1...58.7...3.....5.1......77...3.9..8.24...6......3....4.68...."
+        ).expect("Failed to parse grid");
+        solve(&mut grid, SINGLES);
+
+        let (premise, assumptions) = cell_premises(&grid).find(|(premise, _)| matches!(premise, ForcingPremise::Cell { cell } if cell.0 == 0)).unwrap();
+        let steps = find_for_premise(&grid, 0, &mut HashMap::new(), premise, &assumptions);
+        let deductions: Vec<_> = steps.iter().flat_map(|step| step.deductions(&grid)).map(|deduction| deduction.description(&grid)).collect();
+        assert_eq!(deductions[.. 3], ["3 placed in r1c4", "4 placed in r1c9", "3 placed in r3c2"]);
+        assert!(deductions.contains(&"3 eliminated from r1c1".to_string()));
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+
+use super::aic::{self, Conclusion, ImplicationSearcher, Implications};
+use super::nodes::{group_nodes, value_nodes, ChainNode};
+use super::super::{Deduction, ForcingPremise, Step};
+
+type ImplicationCache<const N: usize> = HashMap<Conclusion<N>, Option<Implications<N>>>;
+
+pub fn find_cell_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    find(grid, cell_premises(grid))
+}
+
+pub fn find_unit_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    find(grid, unit_premises(grid))
+}
+
+pub fn find_digit_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    find(grid, digit_premises(grid))
+}
+
+pub fn cell_premises<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = (ForcingPremise<N>, Vec<Conclusion<N>>)> + 'a {
+    grid.empty_cells().into_iter()
+        .map(move |cell| (ForcingPremise::Cell { cell }, grid.candidates(cell).iter().map(|value| (cell, value, false)).collect()))
+}
+
+pub fn unit_premises<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = (ForcingPremise<N>, Vec<Conclusion<N>>)> + 'a {
+    grid.all_houses().iter()
+        .cartesian_product(grid.all_values().iter().collect::<Vec<_>>())
+        .map(move |(house, value)| (house, value, grid.cells_with_candidate_in(&grid.empty_cells_in(house), value)))
+        .filter(|(_, _, cells)| cells.len() > 1)
+        .map(|(house, value, cells)| (ForcingPremise::Unit { house: house.clone(), value }, cells.iter().map(|cell| (cell, value, false)).collect()))
+}
+
+pub fn digit_premises<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = (ForcingPremise<N>, Vec<Conclusion<N>>)> + 'a {
+    grid.empty_cells().into_iter()
+        .flat_map(move |cell| grid.candidates(cell).iter().map(move |value| (cell, value)).collect::<Vec<_>>())
+        .map(|(cell, value)| (ForcingPremise::Digit { cell, value }, vec![(cell, value, false), (cell, value, true)]))
+}
+
+pub fn deductions<const N: usize>(_grid: &Grid<N>, forcing_chain: &Step<N>) -> Vec<Deduction<N>> {
+    match forcing_chain {
+        Step::ForcingChain { conclusion, .. } => vec![*conclusion],
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, forcing_chain: &Step<N>) -> String {
+    match forcing_chain {
+        Step::ForcingChain { premise, conclusion, branches } => format!(
+            "{} Forcing Chain; {} {}{}", premise_name(premise), premise_description(grid, premise), conclusion.description(grid),
+            branch_tree(branches.iter().map(|branch| aic::description(grid, branch)).collect())
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn find<'a, const N: usize>(grid: &'a Grid<N>, premises: impl Iterator<Item = (ForcingPremise<N>, Vec<Conclusion<N>>)> + 'a) -> impl Iterator<Item = Step<N>> + 'a {
+    let mut searcher = None;
+    let mut cache = HashMap::new();
+    premises.flat_map(move |(premise, assumptions)| {
+        let searcher = searcher.get_or_insert_with(|| {
+            let mut nodes = value_nodes(grid);
+            nodes.append(&mut group_nodes(grid));
+            ImplicationSearcher::for_nodes(grid, nodes, false)
+        });
+        find_for_premise(grid, searcher, &mut cache, premise, &assumptions)
+    })
+}
+
+fn find_for_premise<const N: usize>(grid: &Grid<N>, searcher: &ImplicationSearcher<N>, cache: &mut ImplicationCache<N>, premise: ForcingPremise<N>, assumptions: &[Conclusion<N>]) -> Vec<Step<N>> {
+    for &(cell, value, negated) in assumptions {
+        cache.entry((cell, value, negated)).or_insert_with(|| searcher.implications(&ChainNode::Value { cell, value }, negated));
+    }
+    let Some(branch_implications) = assumptions.iter().map(|assumption| cache[assumption].as_ref()).collect::<Option<Vec<_>>>() else { return vec![] };
+
+    let conclusions = branch_implications.iter()
+        .map(|implications| searcher.conclusions(implications))
+        .reduce(|conclusions, branch_conclusions| &conclusions & &branch_conclusions)
+        .unwrap_or_default();
+
+    conclusions.into_iter()
+        .filter(|&(cell, value, _)| grid.has_candidate(cell, value) && grid.num_candidates(cell) > 1)
+        .sorted_by_key(|&(cell, value, negated)| (negated, cell.0, value.0))
+        .filter_map(|conclusion| {
+            let branches = branch_implications.iter()
+                .map(|implications| searcher.chain_to(implications, conclusion))
+                .collect::<Option<Vec<_>>>()?;
+            let (cell, value, negated) = conclusion;
+            let conclusion = if negated { Deduction::Elimination(cell, value) } else { Deduction::Placement(cell, value) };
+            Some(Step::ForcingChain { premise: premise.clone(), conclusion, branches })
+        })
+        .collect()
+}
+
+pub fn premise_name<const N: usize>(premise: &ForcingPremise<N>) -> &'static str {
+    match premise {
+        ForcingPremise::Cell { .. } => "Cell",
+        ForcingPremise::Unit { .. } => "Unit",
+        ForcingPremise::Digit { .. } => "Digit",
+    }
+}
+
+pub fn premise_description<const N: usize>(grid: &Grid<N>, premise: &ForcingPremise<N>) -> String {
+    match premise {
+        ForcingPremise::Cell { cell } => format!("every candidate of {} implies", grid.cell_name(*cell)),
+        ForcingPremise::Unit { house, value } => format!("every position of {} in {} implies", value.0, grid.cell_set_name(house)),
+        ForcingPremise::Digit { cell, value } => format!("both {}{} and its negation imply", value.0, grid.cell_name(*cell)),
+    }
+}
+
+pub fn branch_tree(branches: Vec<String>) -> String {
+    branches.iter().enumerate()
+        .map(|(idx, branch)| format!("\n  {} {}", if idx + 1 == branches.len() { "└─" } else { "├─" }, branch))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_cell_forcing_chains() {
+        assert_deductions(Strategy::CellForcingChain, &[
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["6 placed in r1c7"]),
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["1 placed in r3c5"]),
+        ]);
+    }
+
+    #[test]
+    fn test_unit_forcing_chains() {
+        assert_deductions(Strategy::UnitForcingChain, &[
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["1 placed in r3c5"]),
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["6 placed in r1c7"]),
+        ]);
+    }
+
+    #[test]
+    fn test_digit_forcing_chains() {
+        assert_deductions(Strategy::DigitForcingChain, &[
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["1 placed in r3c5"]),
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["7 placed in r1c5"]),
+        ]);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellIdx;
+
+use super::aic::{self, ImplicationSearcher, Implications};
+use super::nodes::{group_nodes, value_nodes, ChainNode};
+use super::super::fish;
+use super::super::{Deduction, Step};
+
+type ImplicationCache<const N: usize> = HashMap<(CellIdx<N>, Candidate<N>, bool), Option<Implications<N>>>;
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let mut searcher = None;
+    let mut cache = HashMap::new();
+    (2 ..= N / 2)
+        .flat_map(move |degree| fish::find_kraken_bodies(grid, degree))
+        .flat_map(move |fish| {
+            let searcher = searcher.get_or_insert_with(|| {
+                let mut nodes = value_nodes(grid);
+                nodes.append(&mut group_nodes(grid));
+                ImplicationSearcher::for_nodes(grid, nodes, false)
+            });
+            find_for_fish(grid, searcher, &mut cache, fish)
+        })
+}
+
+pub fn deductions<const N: usize>(_grid: &Grid<N>, kraken_fish: &Step<N>) -> Vec<Deduction<N>> {
+    match kraken_fish {
+        Step::KrakenFish { target: (cell, value), .. } => vec![Deduction::Elimination(*cell, *value)],
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, kraken_fish: &Step<N>) -> String {
+    match kraken_fish {
+        Step::KrakenFish { fish, target: (cell, value), fish_chain, fin_chains } => format!(
+            "Kraken {}; {}{} is eliminated by the fish{} and by every fin ({})",
+            fish::description(grid, fish), value.0, grid.cell_name(*cell),
+            fish_chain.as_ref().map(|chain| format!(" ({})", aic::description(grid, chain))).unwrap_or_default(),
+            fin_chains.iter().map(|chain| aic::description(grid, chain)).join("; ")
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn find_for_fish<const N: usize>(grid: &Grid<N>, searcher: &ImplicationSearcher<N>, cache: &mut ImplicationCache<N>, fish: Step<N>) -> Vec<Step<N>> {
+    let Step::Fish { kind, base, cover, fins: fin_cells, sashimi, value } = &fish else { unreachable!() };
+    let body: Vec<_> = fish::body_eliminations(grid, &fish).iter().map(|cell| (cell, *value)).collect();
+    let fins: Vec<_> = fin_cells.iter().map(|cell| (cell, *value)).collect();
+
+    for (cell, value, negated) in body.iter().map(|&(cell, value)| (cell, value, true)).chain(fins.iter().map(|&(cell, value)| (cell, value, false))) {
+        cache.entry((cell, value, negated)).or_insert_with(|| searcher.implications(&ChainNode::Value { cell, value }, negated));
+    }
+
+    let fish_eliminations: HashSet<_> = body.iter().copied()
+        .chain(body.iter().flat_map(|&(cell, value)| cache[&(cell, value, true)].iter().flat_map(|implications| implications.eliminations.iter().copied())))
+        .collect();
+    let targets: Vec<_> = fins.iter()
+        .map(|&(cell, value)| cache[&(cell, value, false)].as_ref().map(|implications| implications.eliminations.clone()).unwrap_or_default())
+        .fold(fish_eliminations, |targets, fin_eliminations| &targets & &fin_eliminations)
+        .into_iter()
+        .filter(|&(cell, value)| grid.has_candidate(cell, value))
+        .sorted_by_key(|&(cell, value)| (cell.0, value.0))
+        .collect();
+
+    targets.into_iter()
+        .filter_map(|target| {
+            let fish_chain = if body.contains(&target) {
+                None
+            } else {
+                Some(body.iter().find_map(|&(cell, value)| searcher.chain_eliminating(cache[&(cell, value, true)].as_ref()?, target))?)
+            };
+            let fin_chains = fins.iter()
+                .map(|&(cell, value)| searcher.chain_eliminating(cache[&(cell, value, false)].as_ref()?, target))
+                .collect::<Option<Vec<_>>>()?;
+            let fish = Step::Fish { kind: *kind, base: base.clone(), cover: cover.clone(), fins: fin_cells.clone(), sashimi: *sashimi, value: *value };
+            Some(Step::KrakenFish { fish: Box::new(fish), target, fish_chain, fin_chains })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_kraken_fish() {
+        assert_deductions(Strategy::KrakenFish, &[
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &["3 eliminated from r1c7"]),
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &["1 eliminated from r1c5"]),
+            ("..7..8.9.3........4..9....8...2..5...2.5.7.1....8..6.4........3.1.......29.43...6", &["6 eliminated from r3c5"]),
+        ]);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellIdx;
+
+use super::nodes::{is_strongly_linked, is_weakly_linked, value_nodes, ChainNode};
+use super::super::{Deduction, MedusaRule, Step};
+
+type ColouredCandidate<const N: usize> = (CellIdx<N>, Candidate<N>);
+
+const RULES: [MedusaRule; 6] = [
+    MedusaRule::TwiceInCell,
+    MedusaRule::TwiceInHouse,
+    MedusaRule::TwoColoursInCell,
+    MedusaRule::TwoColoursElsewhere,
+    MedusaRule::TwoColoursUnitAndCell,
+    MedusaRule::CellEmptiedByColour,
+];
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    clusters(grid).into_iter()
+        .flat_map(|(on, off)| [(on.clone(), off.clone(), false), (off, on, true)])
+        .cartesian_product(RULES)
+        .filter(|&((_, _, swapped), rule)| !swapped || !matches!(rule, MedusaRule::TwoColoursInCell | MedusaRule::TwoColoursElsewhere))
+        .map(|((on, off, _), rule)| Step::Medusa { rule, on, off })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, medusa: &Step<N>) -> Vec<Deduction<N>> {
+    match medusa {
+        Step::Medusa { rule, on, off } => {
+            let eliminations = match rule {
+                MedusaRule::TwiceInCell => if on.iter().tuple_combinations().any(|(a, b)| a.0 == b.0) { on.clone() } else { vec![] },
+                MedusaRule::TwiceInHouse => if on.iter().tuple_combinations().any(|(a, b)| a.1 == b.1 && grid.neighbours(a.0).contains(b.0)) { on.clone() } else { vec![] },
+                MedusaRule::CellEmptiedByColour => if cell_emptied_by(grid, on, off) { on.clone() } else { vec![] },
+                MedusaRule::TwoColoursInCell => uncoloured_candidates(grid, on, off)
+                    .filter(|&(cell, _)| on.iter().any(|a| a.0 == cell) && off.iter().any(|b| b.0 == cell))
+                    .collect(),
+                MedusaRule::TwoColoursElsewhere => uncoloured_candidates(grid, on, off)
+                    .filter(|&candidate| sees_colour(grid, candidate, on) && sees_colour(grid, candidate, off))
+                    .collect(),
+                MedusaRule::TwoColoursUnitAndCell => uncoloured_candidates(grid, on, off)
+                    .filter(|&candidate| on.iter().any(|a| a.0 == candidate.0) && sees_colour(grid, candidate, off))
+                    .collect(),
+            };
+            eliminations.into_iter().map(|(cell, value)| Deduction::Elimination(cell, value)).collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, medusa: &Step<N>) -> String {
+    match medusa {
+        Step::Medusa { rule, on, off } => format!(
+            "3D Medusa ({}); coloured {} / {}",
+            rule_name(*rule), colour_description(grid, on), colour_description(grid, off)
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn rule_name<'a>(rule: MedusaRule) -> &'a str {
+    match rule {
+        MedusaRule::TwiceInCell => "Colour Twice in Cell",
+        MedusaRule::TwiceInHouse => "Colour Twice in Unit",
+        MedusaRule::TwoColoursInCell => "Two Colours in Cell",
+        MedusaRule::TwoColoursElsewhere => "Two Colours Elsewhere",
+        MedusaRule::TwoColoursUnitAndCell => "Two Colours Unit and Cell",
+        MedusaRule::CellEmptiedByColour => "Cell Emptied by Colour",
+    }
+}
+
+fn colour_description<const N: usize>(grid: &Grid<N>, colour: &[ColouredCandidate<N>]) -> String {
+    format!("({})", colour.iter().map(|&(cell, value)| format!("{}{}", value.0, grid.cell_name(cell))).join(", "))
+}
+
+fn clusters<const N: usize>(grid: &Grid<N>) -> Vec<(Vec<ColouredCandidate<N>>, Vec<ColouredCandidate<N>>)> {
+    let nodes = value_nodes(grid);
+    let mut links: HashMap<usize, Vec<usize>> = HashMap::new();
+    for ((idx1, node1), (idx2, node2)) in nodes.iter().enumerate().tuple_combinations() {
+        if is_weakly_linked(grid, node1, node2) && is_strongly_linked(grid, node1, node2, false) {
+            links.entry(idx1).or_default().push(idx2);
+            links.entry(idx2).or_default().push(idx1);
+        }
+    }
+
+    let mut colours = vec![None; nodes.len()];
+    let mut clusters = Vec::new();
+    for start in 0 .. nodes.len() {
+        if colours[start].is_some() || !links.contains_key(&start) { continue; }
+
+        let mut cluster = (Vec::new(), Vec::new());
+        let mut queue = vec![(start, false)];
+        colours[start] = Some(false);
+        while let Some((idx, colour)) = queue.pop() {
+            let candidate = match nodes[idx] { ChainNode::Value { cell, value } => (cell, value), _ => unreachable!() };
+            if colour { cluster.1.push(candidate) } else { cluster.0.push(candidate) }
+            for &other in &links[&idx] {
+                if colours[other].is_none() {
+                    colours[other] = Some(!colour);
+                    queue.push((other, !colour));
+                }
+            }
+        }
+
+        if cluster.0.len() + cluster.1.len() > 2 { clusters.push(cluster); }
+    }
+
+    clusters
+}
+
+fn uncoloured_candidates<'a, const N: usize>(grid: &'a Grid<N>, on: &'a [ColouredCandidate<N>], off: &'a [ColouredCandidate<N>]) -> impl Iterator<Item = ColouredCandidate<N>> + 'a {
+    grid.empty_cells().into_iter()
+        .flat_map(move |cell| grid.candidates(cell).into_iter().map(move |value| (cell, value)))
+        .filter(move |candidate| !on.contains(candidate) && !off.contains(candidate))
+}
+
+fn sees_colour<const N: usize>(grid: &Grid<N>, (cell, value): ColouredCandidate<N>, colour: &[ColouredCandidate<N>]) -> bool {
+    colour.iter().any(|&(other_cell, other_value)| other_value == value && grid.neighbours(cell).contains(other_cell))
+}
+
+fn cell_emptied_by<const N: usize>(grid: &Grid<N>, on: &[ColouredCandidate<N>], off: &[ColouredCandidate<N>]) -> bool {
+    grid.empty_cells().iter()
+        .filter(|&cell| !on.iter().chain(off).any(|&(coloured_cell, _)| coloured_cell == cell))
+        .any(|cell| grid.candidates(cell).iter().all(|value| sees_colour(grid, (cell, value), on)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_medusa() {
+        assert_deductions(Strategy::Medusa, &[
+            ("..1..2........74528...........4937.89..8.............5..2.19....7.....4.6..27.8..", &[
+                "3 eliminated from r1c7", "3 eliminated from r6c8", "3 eliminated from r9c9", "1 eliminated from r3c9", "7 eliminated from r7c9",
+                "6 eliminated from r7c8", "1 eliminated from r5c8", "1 eliminated from r3c7", "9 eliminated from r3c8", "6 eliminated from r5c7",
+                "6 eliminated from r1c9", "7 eliminated from r3c8", "1 eliminated from r9c8", "9 eliminated from r6c7",
+            ]),
+            ("....7.943..25..1..7......5.84...62.....9..7....54...6..58..4..1...6..........5..7", &["1 eliminated from r3c2"]),
+            (".......9..5.6.4.8.48....25....9..82....3.....2.6.....57....64.....4.5....6...8..9", &[
+                "1 eliminated from r1c2", "1 eliminated from r8c7", "7 eliminated from r9c8", "3 eliminated from r9c5", "3 eliminated from r8c1",
+                "7 eliminated from r8c5", "7 eliminated from r6c6", "1 eliminated from r4c6", "7 eliminated from r4c3", "7 eliminated from r5c9",
+                "7 eliminated from r2c3", "1 eliminated from r9c1", "1 eliminated from r6c8", "1 eliminated from r2c9", "7 eliminated from r1c7",
+                "7 eliminated from r5c2",
+            ]),
+            ("..482.9....1..9.6....3..5..4.5...2.9.......1.8...6...42....8....3......8......15.", &["7 eliminated from r9c4"]),
+            (".142..98...37.1...8....4......3....9..6.52......9...57.32...8.4.......9........21", &["1 eliminated from r7c5"]),
+        ]);
+    }
+}
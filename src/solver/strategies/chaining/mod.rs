@@ -1,7 +1,15 @@
 mod aic;
+mod als;
+mod death_blossom;
+mod dynamic;
+mod forcing;
+mod kraken;
+mod medusa;
 mod nodes;
 
 pub use aic::Aic;
+pub use dynamic::DynamicChain;
+pub use nodes::almost_locked_sets;
 
 use nodes::{als_nodes, bivalue_nodes, group_nodes, group_nodes_for_candidate, value_nodes, value_nodes_for_candidate};
 
@@ -35,12 +43,55 @@ pub fn find_als_aics<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Ite
     aic::find_aics(grid, nodes, false).map(|aic| Step::AlsAic { aic })
 }
 
+pub fn find_medusas<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    medusa::find(grid)
+}
+
+pub fn find_als_xzs<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    als::find_als_xzs(grid)
+}
+
+pub fn find_als_xy_wings<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    als::find_als_xy_wings(grid)
+}
+
+pub fn find_kraken_fish<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    kraken::find(grid)
+}
+
+pub fn find_death_blossoms<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    death_blossom::find(grid)
+}
+
+pub fn find_cell_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    forcing::find_cell_forcing_chains(grid)
+}
+
+pub fn find_unit_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    forcing::find_unit_forcing_chains(grid)
+}
+
+pub fn find_digit_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    forcing::find_digit_forcing_chains(grid)
+}
+
+pub fn find_dynamic_forcing_chains<'a, const N: usize>(grid: &'a Grid<N>, level: usize) -> impl Iterator<Item = Step<N>> + 'a {
+    dynamic::find(grid, level)
+}
+
 pub fn deductions<const N: usize>(grid: &Grid<N>, chain_step: &Step<N>) -> Vec<Deduction<N>> {
     match chain_step {
         Step::XYChain { aic } => aic::deductions(grid, aic),
         Step::XChain { aic } => aic::deductions(grid, aic),
         Step::Aic { aic } => aic::deductions(grid, aic),
         Step::AlsAic { aic } => aic::deductions(grid, aic),
+        medusa @ Step::Medusa { .. } => medusa::deductions(grid, medusa),
+        als_xz @ Step::AlsXz { .. } => als::deductions(grid, als_xz),
+        als_xy_wing @ Step::AlsXyWing { .. } => als::deductions(grid, als_xy_wing),
+        death_blossom @ Step::DeathBlossom { .. } => death_blossom::deductions(grid, death_blossom),
+        kraken_fish @ Step::KrakenFish { .. } => kraken::deductions(grid, kraken_fish),
+        forcing_chain @ Step::ForcingChain { .. } => forcing::deductions(grid, forcing_chain),
+        dynamic_chain @ Step::DynamicForcingChain { .. } => dynamic::deductions(grid, dynamic_chain),
         _ => unreachable!(),
     }
 }
@@ -51,6 +102,13 @@ pub fn description<const N: usize>(grid: &Grid<N>, chain_step: &Step<N>) -> Stri
         Step::XChain { aic } => format!("X-Chain; {}", aic::description(grid, aic)),
         Step::Aic { aic } => format!("AIC; {}", aic::description(grid, aic)),
         Step::AlsAic { aic } => format!("ALS-AIC; {}", aic::description(grid, aic)),
+        medusa @ Step::Medusa { .. } => medusa::description(grid, medusa),
+        als_xz @ Step::AlsXz { .. } => als::description(grid, als_xz),
+        als_xy_wing @ Step::AlsXyWing { .. } => als::description(grid, als_xy_wing),
+        death_blossom @ Step::DeathBlossom { .. } => death_blossom::description(grid, death_blossom),
+        kraken_fish @ Step::KrakenFish { .. } => kraken::description(grid, kraken_fish),
+        forcing_chain @ Step::ForcingChain { .. } => forcing::description(grid, forcing_chain),
+        dynamic_chain @ Step::DynamicForcingChain { .. } => dynamic::description(grid, dynamic_chain),
         _ => unreachable!(),
     }
 }
@@ -102,22 +102,29 @@ pub fn group_nodes<const N: usize>(grid: &Grid<N>) -> Vec<ChainNode<N>> {
 }
 
 pub fn als_nodes<const N: usize>(grid: &Grid<N>) -> Vec<ChainNode<N>> {
-    let mut nodes = HashSet::new();
+    almost_locked_sets(grid).into_iter()
+        .filter(|(cells, _)| cells.len() > 1)
+        .flat_map(|(cells, candidates)| candidates.into_iter().map(move |value| {
+            ChainNode::Als { cells: cells.clone(), cells_with_value: grid.cells_with_candidate_in(&cells, value), value }
+        }))
+        .collect()
+}
+
+pub fn almost_locked_sets<const N: usize>(grid: &Grid<N>) -> Vec<(CellSet<N>, CandidateSet<N>)> {
+    let mut seen = HashSet::new();
+    let mut sets = Vec::new();
     for house in grid.all_houses() {
         let empty_cells = grid.empty_cells_in(house);
-        for degree in 2 .. empty_cells.len() {
+        for degree in 1 .. empty_cells.len() {
             for cells in empty_cells.iter().combinations(degree).map(CellSet::from_cells) {
                 let candidates = CandidateSet::union(cells.iter().map(|cell| grid.candidates(cell)));
-                if candidates.len() == degree + 1 {
-                    for value in candidates.iter() {
-                        let node = ChainNode::Als { cells: cells.clone(), cells_with_value: grid.cells_with_candidate_in(&cells, value), value };
-                        if !nodes.contains(&node) { nodes.insert(node); }
-                    }
+                if candidates.len() == degree + 1 && seen.insert(cells.clone()) {
+                    sets.push((cells, candidates));
                 }
             }
         }
     }
-    nodes.into_iter().collect()
+    sets
 }
 
 fn is_weakly_linked_value_value<const N: usize>(grid: &Grid<N>, start_node: &ChainNode<N>, end_node: &ChainNode<N>) -> bool {
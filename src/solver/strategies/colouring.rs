@@ -0,0 +1,131 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find_simple_colourings<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    grid.all_values().into_iter()
+        .flat_map(move |value| clusters(grid, value).into_iter().map(move |(on, off)| Step::SimpleColouring { value, on, off }))
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn find_multi_colourings<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    grid.all_values().into_iter()
+        .flat_map(move |value| {
+            let clusters = clusters(grid, value);
+            clusters.iter().permutations(2)
+                .flat_map(|pair| {
+                    let ((a_on, a_off), (b_on, b_off)) = (pair[0], pair[1]);
+                    [(a_on, a_off, b_on, b_off), (a_on, a_off, b_off, b_on)]
+                })
+                .filter(|&(a_on, _, b_on, _)| grid.sees_any(a_on, b_on))
+                .map(|(a_on, a_off, b_on, b_off)| Step::MultiColouring {
+                    value, colours1: (a_on.clone(), a_off.clone()), colours2: (b_on.clone(), b_off.clone()),
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, colouring: &Step<N>) -> Vec<Deduction<N>> {
+    match colouring {
+        Step::SimpleColouring { value, on, off } => {
+            let elimination_cells = match wrapped_colour(grid, on, off) {
+                Some(colour) => colour.clone(),
+                None => grid.cells_with_candidate_in(&(grid.seen_by(on) & grid.seen_by(off)), *value) & !(on | off),
+            };
+            elimination_cells.iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
+        },
+        Step::MultiColouring { value, colours1: (a_on, a_off), colours2: (b_on, b_off) } => {
+            let coloured_cells = a_on | a_off | b_on | b_off;
+            let mut elimination_cells = grid.cells_with_candidate_in(&(grid.seen_by(a_off) & grid.seen_by(b_off)), *value) & !coloured_cells;
+            if grid.sees_any(a_on, b_off) { elimination_cells |= a_on; }
+            elimination_cells.iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, colouring: &Step<N>) -> String {
+    match colouring {
+        Step::SimpleColouring { value, on, off } => format!(
+            "Simple Colouring ({}); {} coloured {} / {}",
+            if wrapped_colour(grid, on, off).is_some() { "Colour Wrap" } else { "Colour Trap" },
+            value.0, grid.cell_set_name(on), grid.cell_set_name(off)
+        ),
+        Step::MultiColouring { value, colours1: (a_on, a_off), colours2: (b_on, b_off) } => format!(
+            "Multi-Colouring; {} coloured {} / {} and {} / {}, where {} and {} cannot both be true",
+            value.0, grid.cell_set_name(a_on), grid.cell_set_name(a_off), grid.cell_set_name(b_on), grid.cell_set_name(b_off),
+            grid.cell_set_name(a_on), grid.cell_set_name(b_on)
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn clusters<const N: usize>(grid: &Grid<N>, value: Candidate<N>) -> Vec<(CellSet<N>, CellSet<N>)> {
+    let conjugate_pairs: Vec<(CellIdx<N>, CellIdx<N>)> = grid.all_houses().iter()
+        .map(|house| grid.cells_with_candidate_in(house, value))
+        .filter(|cells| cells.len() == 2)
+        .map(|cells| cells.iter().collect_tuple().unwrap())
+        .collect();
+
+    let mut uncoloured = CellSet::from_cells(conjugate_pairs.iter().flat_map(|&(cell1, cell2)| [cell1, cell2]));
+    let mut clusters = Vec::new();
+    while let Some(start) = uncoloured.first() {
+        let mut colours = [CellSet::from_cells([start]), CellSet::empty()];
+        let mut queue = vec![(start, 0)];
+        uncoloured.remove_cell(start);
+
+        while let Some((cell, colour)) = queue.pop() {
+            for &(cell1, cell2) in &conjugate_pairs {
+                let other = if cell1 == cell { cell2 } else if cell2 == cell { cell1 } else { continue };
+                if uncoloured.contains(other) {
+                    uncoloured.remove_cell(other);
+                    colours[1 - colour].add_cell(other);
+                    queue.push((other, 1 - colour));
+                }
+            }
+        }
+
+        let [on, off] = colours;
+        if on.len() + off.len() > 2 { clusters.push((on, off)); }
+    }
+
+    clusters
+}
+
+fn wrapped_colour<'a, const N: usize>(grid: &Grid<N>, on: &'a CellSet<N>, off: &'a CellSet<N>) -> Option<&'a CellSet<N>> {
+    [on, off].into_iter().find(|colour| grid.sees_any(colour, colour))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_simple_colouring() {
+        assert_deductions(Strategy::SimpleColouring, &[
+            ("...3....8.2.....97...9..6...4...3...9...8......6..518....71...95.2......7.4.5..2.", &[
+                "1 eliminated from r1c2", "1 eliminated from r3c2", "1 eliminated from r5c2",
+            ]),
+            ("4.....25.....82.....1.6...3....93..692...6..........9.....315...68.5..4.1.4......", &[
+                "1 eliminated from r1c5", "1 eliminated from r2c8", "1 eliminated from r4c4", "1 eliminated from r5c5", "1 eliminated from r6c2",
+                "1 eliminated from r6c9",
+            ]),
+        ]);
+    }
+
+    #[test]
+    fn test_multi_colouring() {
+        assert_deductions(Strategy::MultiColouring, &[
+            ("......593.2...3..18........2...4..5......28..4...7.31....8.91....31...7..457.....", &["1 eliminated from r5c2"]),
+            ("51.7........36...9..9...16.....4..5.......9...53..98..94.1....2.2....7..73.....4.", &["2 eliminated from r1c6", "2 eliminated from r2c8"]),
+            ("......7...7.1.....5.1...6...8......5..64..1.3...598....2..7.3....79...14.1...4.6.", &["2 eliminated from r4c3", "2 eliminated from r6c7"]),
+        ]);
+    }
+}
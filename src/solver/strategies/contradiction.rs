@@ -0,0 +1,125 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::{CellIdx, CellSet};
+use crate::solver::{solve, SolveResult};
+
+use super::{Deduction, Step, Strategy};
+
+pub fn find_nishios<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    grid.empty_cells().into_iter()
+        .flat_map(move |cell| grid.candidates(cell).iter().map(move |value| (cell, value)).collect::<Vec<_>>())
+        .filter_map(move |(cell, value)| nishio(grid, cell, value))
+}
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>, depth: usize, strategies: &'static [Strategy]) -> impl Iterator<Item = Step<N>> + 'a {
+    let mut inner_strategies = strategies.to_vec();
+    if depth > 1 { inner_strategies.push(Strategy::Contradiction(depth - 1, strategies)); }
+
+    grid.empty_cells().into_iter()
+        .flat_map(move |cell| grid.candidates(cell).iter().map(move |value| (cell, value)).collect::<Vec<_>>())
+        .filter_map(move |(cell, value)| {
+            let mut assumed = grid.clone();
+            assumed.place_value(cell, value);
+            let solve_details = solve(&mut assumed, &inner_strategies);
+            (solve_details.result == SolveResult::Contradiction).then(|| Step::Contradiction { cell, value, depth, trace: solve_details.steps })
+        })
+}
+
+pub fn deductions<const N: usize>(_grid: &Grid<N>, contradiction: &Step<N>) -> Vec<Deduction<N>> {
+    match contradiction {
+        Step::Nishio { cell, value, .. } => vec![Deduction::Elimination(*cell, *value)],
+        Step::Contradiction { cell, value, .. } => vec![Deduction::Elimination(*cell, *value)],
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, contradiction: &Step<N>) -> String {
+    match contradiction {
+        Step::Nishio { cell, value, trace } => format!(
+            "Nishio; assuming {}{} leads to a contradiction{}", value.0, grid.cell_name(*cell), trace_description(grid, trace)
+        ),
+        Step::Contradiction { cell, value, depth, trace } => format!(
+            "Contradiction (depth {}); assuming {}{} leads to a contradiction{}", depth, value.0, grid.cell_name(*cell), trace_description(grid, trace)
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn nishio<const N: usize>(grid: &Grid<N>, cell: CellIdx<N>, value: Candidate<N>) -> Option<Step<N>> {
+    let mut forced = CellSet::empty();
+    let mut trace = Vec::new();
+    let mut positions = &grid.cells_with_candidate(value) & !grid.neighbours(cell);
+    loop {
+        let mut progress = false;
+        for house in grid.all_houses() {
+            if house.contains(cell) || house.intersects(&forced) || house.iter().any(|other| grid.value(other) == Some(value)) { continue; }
+            let house_positions = house & &positions;
+            match house_positions.len() {
+                0 => {
+                    trace.push((Step::NoPlaceForCandidateInHouse { house: house.clone(), value }, vec![Deduction::Contradiction]));
+                    return Some(Step::Nishio { cell, value, trace });
+                },
+                1 => {
+                    let forced_cell = house_positions.first().unwrap();
+                    forced = &forced | &CellSet::from_cells([forced_cell]);
+                    trace.push((Step::HiddenSingle { house: house.clone(), cell: forced_cell, value }, vec![Deduction::Placement(forced_cell, value)]));
+                    positions = &positions & !grid.neighbours(forced_cell);
+                    progress = true;
+                },
+                _ => {},
+            }
+        }
+        if !progress { return None; }
+    }
+}
+
+fn trace_description<const N: usize>(grid: &Grid<N>, trace: &[(Step<N>, Vec<Deduction<N>>)]) -> String {
+    trace.iter()
+        .map(|(step, deductions)| format!(
+            "\n  - {} ({})", step.description(grid).replace('\n', "\n    "), deductions.iter().map(|deduction| deduction.description(grid)).join(", ")
+        ))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_nishio() {
+        assert_deductions(Strategy::Nishio, &[
+            ("..7...964..4.2..........5....17.63....89.........3.....52...8..64.8...12....9..5.", &["1 eliminated from r1c1"]),
+            (".....328.18.6.......5..9........219.2...4.3...4.9..7....7..4.3........5..9.1.8...", &["3 eliminated from r4c1"]),
+            (".........59.4...2...6.1...7.18...........8.56.......3.2..39.....6..2.3...39681..2", &["3 eliminated from r1c1"]),
+        ]);
+    }
+
+    #[test]
+    fn test_contradiction() {
+        assert_deductions(Strategy::Contradiction(2, SINGLES), &[
+            ("....3...4.3.7...2...25...7...591.2.3.6.......9.1...8.....183..55.6.9.........4...", &["1 eliminated from r1c1"]),
+            ("..7...964..4.2..........5....17.63....89.........3.....52...8..64.8...12....9..5.", &["1 eliminated from r1c1"]),
+        ]);
+    }
+
+    #[test]
+    fn test_nishio_trace() {
+        let mut grid = Grid::<9>::from_empty_grid_and_string(
+            Grid::empty_classic(), ".....328.18.6.......5..9........219.2...4.3...4.9..7....7..4.3........5..9.1.8..."
+        ).expect("Failed to parse grid");
+        solve(&mut grid, SINGLES);
+
+        let step = Strategy::Nishio.find_steps(&grid).next().expect("No Nishio found");
+        assert_eq!(step.description(&grid), "Nishio; assuming 3r4c1 leads to a contradiction\
+            \n  - Hidden Single; r6c5 is the only place for 3 in Region 5 (3 placed in r6c5)\
+            \n  - Hidden Single; r8c2 is the only place for 3 in Region 7 (3 placed in r8c2)\
+            \n  - No place for 3 in Region 8 (Contradiction!)");
+    }
+}
@@ -0,0 +1,108 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::CandidateSet;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    grid.regions().iter()
+        .cartesian_product([(grid.rows(), grid.columns()), (grid.columns(), grid.rows())])
+        .flat_map(move |(region, (lines, cross_lines))| {
+            lines.iter()
+                .filter(|line| line.intersects(region))
+                .flat_map(|line| find_for_base_line(grid, region, line, lines, cross_lines))
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, exocet: &Step<N>) -> Vec<Deduction<N>> {
+    match exocet {
+        Step::Exocet { base, targets, values } => {
+            let target_values = grid.candidates_in(targets);
+            let base_eliminations = base.iter()
+                .flat_map(|cell| (grid.candidates(cell) & !&target_values).iter().map(move |value| Deduction::Elimination(cell, value)).collect::<Vec<_>>());
+            let target_eliminations = targets.iter()
+                .flat_map(|cell| (grid.candidates(cell) & !values).iter().map(move |value| Deduction::Elimination(cell, value)).collect::<Vec<_>>());
+            base_eliminations.chain(target_eliminations).collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, exocet: &Step<N>) -> String {
+    match exocet {
+        Step::Exocet { base, targets, values } => format!(
+            "Junior Exocet; base cells {} and target cells {} must hold the same two of {}",
+            grid.cell_set_name(base), grid.cell_set_name(targets), values
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn find_for_base_line<const N: usize>(grid: &Grid<N>, region: &CellSet<N>, line: &CellSet<N>, lines: &[CellSet<N>], cross_lines: &[CellSet<N>]) -> Vec<Step<N>> {
+    let band_lines: Vec<_> = lines.iter().filter(|other| other.intersects(region)).collect();
+    let parallel_lines: Vec<_> = band_lines.iter().copied().filter(|&other| other != line).collect();
+    let band_regions: Vec<_> = grid.regions().iter().filter(|&other| other != region && other.intersects(line)).collect();
+    let band = CellSet::union(band_lines.iter().copied());
+    if parallel_lines.len() != 2 || band_regions.len() != 2 || band != CellSet::union(band_regions.iter().copied().chain([region])) {
+        return vec![];
+    }
+
+    grid.empty_cells_in(&(line & region)).iter().tuple_combinations()
+        .map(|(base1, base2)| CellSet::from_cells([base1, base2]))
+        .filter(|base| (3 ..= 4).contains(&grid.candidates_in(base).len()))
+        .flat_map(|base| {
+            let values = grid.candidates_in(&base);
+            let Some(escape_line) = cross_lines.iter().find(|cross_line| cross_line.intersects(&(line & region)) && !cross_line.intersects(&base)) else { return vec![] };
+            [(parallel_lines[0], parallel_lines[1]), (parallel_lines[1], parallel_lines[0])].into_iter()
+                .cartesian_product([(band_regions[0], band_regions[1]), (band_regions[1], band_regions[0])])
+                .flat_map(|((line1, line2), (region1, region2))| {
+                    grid.empty_cells_in(&(line1 & region1)).iter().cartesian_product(grid.empty_cells_in(&(line2 & region2)).iter().collect::<Vec<_>>())
+                        .filter(|&(target1, target2)| target1.0 < target2.0)
+                        .filter(|&(target1, target2)| {
+                            let (cross_line1, cross_line2) = (cross_line_of(cross_lines, target1), cross_line_of(cross_lines, target2));
+                            let companions = &(cross_line1 & line2) | &(cross_line2 & line1);
+                            let s_cells = &(&(cross_line1 | cross_line2) | escape_line) & !&band;
+                            grid.candidates(target1).intersects(&values) && grid.candidates(target2).intersects(&values)
+                                && companions.iter().all(|cell| !holds_any(grid, cell, &values))
+                                && values.iter().all(|value| {
+                                    let value_cells = CellSet::from_cells(s_cells.iter().filter(|&cell| holds_any(grid, cell, &CandidateSet::from_candidates([value]))));
+                                    lines.iter().filter(|other| other.intersects(&value_cells)).count() <= 2
+                                })
+                        })
+                        .map(|(target1, target2)| Step::Exocet { base: base.clone(), targets: CellSet::from_cells([target1, target2]), values: values.clone() })
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn holds_any<const N: usize>(grid: &Grid<N>, cell: CellIdx<N>, values: &CandidateSet<N>) -> bool {
+    grid.value(cell).map_or_else(|| grid.candidates(cell).intersects(values), |value| values.contains(value))
+}
+
+fn cross_line_of<const N: usize>(cross_lines: &[CellSet<N>], cell: CellIdx<N>) -> &CellSet<N> {
+    cross_lines.iter().find(|cross_line| cross_line.contains(cell)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_exocet() {
+        assert_deductions(Strategy::Exocet, &[
+            ("98.7.....6.....9....5....7..4..3..2...85..4.......4..1..69..5......2...3.....1.4.", &["6 eliminated from r1c7", "8 eliminated from r2c4"]),
+            ("98.7.....7...6......5..87..5....69....43...6.....2...1.5...48.....6....3....1..2.", &["7 eliminated from r4c2"]),
+            ("98.7.....7...6.9....5..9.7.5....6.8...4.3.6.....2....1.5...7.4....6....3....1.2..", &[
+                "7 eliminated from r4c2", "9 eliminated from r4c2", "8 eliminated from r7c1",
+            ]),
+        ]);
+    }
+}
@@ -4,24 +4,57 @@ use crate::grid::{Grid, House};
 use crate::grid::candidate::Candidate;
 use crate::grid::cell::CellSet;
 
-use super::{Deduction, Step};
+use super::{Deduction, FishKind, Step};
 
-pub fn find<'a, const N: usize>(grid: &'a Grid<N>, degree: usize, finned: bool) -> impl Iterator<Item = Step<N>> + 'a {
+const MAX_KRAKEN_FINS: usize = 3;
+
+#[derive(Copy, Clone, PartialEq)]
+enum FinRule {
+    Unfinned,
+    Finned,
+    Kraken,
+}
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>, kind: FishKind, degree: usize, finned: bool) -> impl Iterator<Item = Step<N>> + 'a {
+    find_with_fins(grid, kind, degree, if finned { FinRule::Finned } else { FinRule::Unfinned })
+}
+
+pub fn find_finned<'a, const N: usize>(grid: &'a Grid<N>, kind: FishKind, degree: usize, sashimi: bool) -> impl Iterator<Item = Step<N>> + 'a {
+    find(grid, kind, degree, true).filter(move |fish| matches!(fish, Step::Fish { sashimi: is_sashimi, .. } if *is_sashimi == sashimi))
+}
+
+pub fn find_kraken_bodies<'a, const N: usize>(grid: &'a Grid<N>, degree: usize) -> impl Iterator<Item = Step<N>> + 'a {
+    find_with_fins(grid, FishKind::Basic, degree, FinRule::Kraken)
+}
+
+pub fn body_eliminations<const N: usize>(grid: &Grid<N>, fish: &Step<N>) -> CellSet<N> {
+    match fish {
+        Step::Fish { base, cover, value, .. } => {
+            let candidate_positions = grid.cells_with_candidate(*value);
+            (CellSet::union(cover) & &candidate_positions) & !(CellSet::union(base) & &candidate_positions)
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn find_with_fins<'a, const N: usize>(grid: &'a Grid<N>, kind: FishKind, degree: usize, fin_rule: FinRule) -> impl Iterator<Item = Step<N>> + 'a {
     grid.all_values().into_iter().flat_map(move |value| {
-        [House::Row, House::Column].into_iter().flat_map(move |base_type| {
-            let candidate_positions = grid.cells_with_candidate(value);
-            let all_base_sets = grid.group_by(&candidate_positions, base_type);
-            all_base_sets.into_iter().combinations(degree).flat_map(move |base_sets|
-                find_for_base_and_value(grid, degree, &candidate_positions, &base_sets, base_type, value, finned)
-            )
+        let candidate_positions = grid.cells_with_candidate(value);
+        house_types(kind).into_iter().flat_map(move |(base_types, cover_types)| {
+            let base_houses: Vec<_> = houses_of_types(grid, &base_types).into_iter().filter(|house| house.intersects(&candidate_positions)).collect();
+            let cover_houses = houses_of_types(grid, &cover_types);
+            base_houses.into_iter().combinations(degree)
+                .filter(|base| base.iter().tuple_combinations().all(|(house1, house2)| !(*house1 & *house2).intersects(&candidate_positions)))
+                .flat_map(|base| find_for_base_and_value(grid, kind, &candidate_positions, &base, &cover_houses, value, fin_rule))
+                .collect::<Vec<_>>()
         })
     })
 }
 
 pub fn deductions<const N: usize>(grid: &Grid<N>, fish: &Step<N>) -> Vec<Deduction<N>> {
     match fish {
-        Step::Fish { base, cover, fins, value, .. } => {
-            (grid.common_neighbours(fins) & cover & !base).iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
+        Step::Fish { fins, value, .. } => {
+            (grid.common_neighbours(fins) & body_eliminations(grid, fish)).iter().map(|cell| Deduction::Elimination(cell, *value)).collect()
         },
         _ => unreachable!(),
     }
@@ -29,24 +62,15 @@ pub fn deductions<const N: usize>(grid: &Grid<N>, fish: &Step<N>) -> Vec<Deducti
 
 pub fn description<const N: usize>(grid: &Grid<N>, fish: &Step<N>) -> String {
     match fish {
-        Step::Fish { base_type, base, cover, fins, value } => {
-            let base_houses = match base_type { 
-                House::Row => grid.intersecting_rows(base), 
-                House::Column => grid.intersecting_columns(base), 
-                _ => unreachable!() 
-            };
-            let cover_houses = match base_type { 
-                House::Row => grid.intersecting_columns(cover), 
-                House::Column => grid.intersecting_rows(cover), 
-                _ => unreachable!() 
-            };
+        Step::Fish { kind, base, cover, fins, sashimi, value } => {
             format!(
-                "{}{}; on value {} with base ({}), cover ({}){}{}",
-                if fins.is_empty() { "" } else { "Finned " },
-                fish_name(base_houses.len()),
+                "{}{}{}; on value {} with base ({}), cover ({}){}{}",
+                if *sashimi { "Sashimi " } else if fins.is_empty() { "" } else { "Finned " },
+                match kind { FishKind::Basic => "", FishKind::Franken => "Franken ", FishKind::Mutant => "Mutant " },
+                fish_name(base.len()),
                 value.0,
-                base_houses.iter().map(|house| grid.cell_set_name(house)).join(", "),
-                cover_houses.iter().map(|house| grid.cell_set_name(house)).join(", "),
+                base.iter().map(|house| grid.cell_set_name(house)).join(", "),
+                cover.iter().map(|house| grid.cell_set_name(house)).join(", "),
                 if fins.is_empty() { "" } else { " and fins " },
                 if fins.is_empty() { "".to_string() } else { grid.cell_set_name(fins) },
             )
@@ -55,42 +79,114 @@ pub fn description<const N: usize>(grid: &Grid<N>, fish: &Step<N>) -> String {
     }
 }
 
-fn find_for_base_and_value<const N: usize>(grid: &Grid<N>, degree: usize, candidate_positions: &CellSet<N>, base_sets: &[CellSet<N>], base_type: House, value: Candidate<N>, finned: bool) -> Vec<Step<N>> {
-    
-    let base_union = CellSet::union(base_sets);
-    let cover_sets = match base_type {
-        House::Row => grid.intersecting_columns(&base_union),
-        House::Column => grid.intersecting_rows(&base_union),
-        _ => unreachable!(),
+fn house_types(kind: FishKind) -> Vec<(Vec<House>, Vec<House>)> {
+    match kind {
+        FishKind::Basic => vec![
+            (vec![House::Row], vec![House::Column]),
+            (vec![House::Column], vec![House::Row]),
+        ],
+        FishKind::Franken => vec![
+            (vec![House::Row, House::Region], vec![House::Column, House::Region]),
+            (vec![House::Column, House::Region], vec![House::Row, House::Region]),
+        ],
+        FishKind::Mutant => vec![
+            (vec![House::Row, House::Column, House::Region], vec![House::Row, House::Column, House::Region]),
+        ],
+    }
+}
+
+fn houses_of_types<'a, const N: usize>(grid: &'a Grid<N>, house_types: &[House]) -> Vec<&'a CellSet<N>> {
+    house_types.iter().flat_map(|&house_type| grid.houses(house_type)).collect()
+}
+
+fn fish_kind<const N: usize>(grid: &Grid<N>, base: &[CellSet<N>], cover: &[CellSet<N>]) -> FishKind {
+    let all_of_types = |houses: &[CellSet<N>], house_types: &[House]| {
+        houses.iter().all(|house| house_types.iter().any(|&house_type| grid.houses(house_type).contains(house)))
     };
+    let is_kind = |kind| house_types(kind).iter().any(|(base_types, cover_types)| all_of_types(base, base_types) && all_of_types(cover, cover_types));
+
+    if is_kind(FishKind::Basic) { FishKind::Basic }
+    else if is_kind(FishKind::Franken) { FishKind::Franken }
+    else { FishKind::Mutant }
+}
+
+fn find_for_base_and_value<const N: usize>(grid: &Grid<N>, kind: FishKind, candidate_positions: &CellSet<N>, base: &[&CellSet<N>], cover_houses: &[&CellSet<N>], value: Candidate<N>, fin_rule: FinRule) -> Vec<Step<N>> {
+    let base_union = CellSet::union(base.iter().copied()) & candidate_positions;
+    let cover_houses: Vec<_> = cover_houses.iter()
+        .filter(|house| !base.contains(house) && house.intersects(&base_union))
+        .copied()
+        .collect();
 
-    if !finned && cover_sets.len() == degree {
-        let cover_union = CellSet::union(cover_sets) & candidate_positions;
-        if cover_union.intersects(!&base_union) {
-            vec![Step::Fish { base_type, base: base_union, cover: cover_union, fins: CellSet::empty(), value }]
-        } else {
-            vec![]
+    let search = CoverSearch { grid, degree: base.len(), candidate_positions, base_union: &base_union, cover_houses: &cover_houses, fin_rule };
+    let fins = if fin_rule == FinRule::Unfinned { None } else { Some(CellSet::empty()) };
+    search.covers(vec![], CellSet::empty(), vec![], fins).into_iter()
+        .map(|cover| {
+            let cover: Vec<_> = cover.into_iter().map(|idx| cover_houses[idx].clone()).collect();
+            let fins = &base_union & !CellSet::union(&cover);
+            let base: Vec<_> = base.iter().map(|&house| house.clone()).collect();
+            let sashimi = !fins.is_empty() && base.iter().any(|house| (&(house & &base_union) & !&fins).len() < 2);
+            Step::Fish { kind: fish_kind(grid, &base, &cover), base, cover, fins, sashimi, value }
+        })
+        .filter(|fish| matches!(fish, Step::Fish { kind: fish_kind, .. } if *fish_kind == kind))
+        .collect()
+}
+
+struct CoverSearch<'a, const N: usize> {
+    grid: &'a Grid<N>,
+    degree: usize,
+    candidate_positions: &'a CellSet<N>,
+    base_union: &'a CellSet<N>,
+    cover_houses: &'a [&'a CellSet<N>],
+    fin_rule: FinRule,
+}
+
+impl<const N: usize> CoverSearch<'_, N> {
+    fn covers(&self, cover: Vec<usize>, covered: CellSet<N>, excluded: Vec<usize>, fins: Option<CellSet<N>>) -> Vec<Vec<usize>> {
+        if cover.len() == self.degree {
+            return if self.has_eliminations(&covered) { vec![cover] } else { vec![] };
         }
-    }
 
-    else if finned && cover_sets.len() > degree {
-        let num_fins = cover_sets.len() - degree;
-        let full_cover = CellSet::union(&cover_sets) & candidate_positions;
-        let mut finned_fish = vec![];
-
-        for ex_covers in cover_sets.iter().combinations(num_fins) {
-            let uncovered = CellSet::union(ex_covers);
-            let cover_union = &full_cover & !&uncovered;
-            let fins = &base_union & &uncovered;
-            if grid.common_neighbours(&fins).intersects(&(&cover_union & !&base_union)) {
-                finned_fish.push(Step::Fish { base_type, base: base_union.clone(), cover: cover_union, fins: fins, value });
+        let marked_fins = fins.clone().unwrap_or_else(CellSet::empty);
+        let Some(cell) = (self.base_union & !(&covered | &marked_fins)).first() else { return vec![] };
+        let options: Vec<_> = (0 .. self.cover_houses.len())
+            .filter(|idx| !excluded.contains(idx) && self.cover_houses[*idx].contains(cell))
+            .filter(|&idx| !marked_fins.iter().any(|fin| self.cover_houses[idx].contains(fin)))
+            .collect();
+
+        let mut results = Vec::new();
+        for (num_excluded, &idx) in options.iter().enumerate() {
+            let mut cover = cover.clone();
+            cover.push(idx);
+            let mut excluded = excluded.clone();
+            excluded.extend(&options[.. num_excluded]);
+            results.extend(self.covers(cover, &covered | self.cover_houses[idx], excluded, fins.clone()));
+        }
+
+        if let Some(mut fins) = fins {
+            fins.add_cell(cell);
+            let is_viable = match self.fin_rule {
+                FinRule::Kraken => fins.len() <= MAX_KRAKEN_FINS,
+                _ => (self.grid.common_neighbours(&fins) & self.candidate_positions).intersects(!self.base_union),
+            };
+            if is_viable {
+                let mut excluded = excluded;
+                excluded.extend(options);
+                results.extend(self.covers(cover, covered, excluded, Some(fins)));
             }
         }
 
-        finned_fish
+        results
     }
 
-    else { vec![] }
+    fn has_eliminations(&self, covered: &CellSet<N>) -> bool {
+        let fins = self.base_union & !covered;
+        let eliminations = &(covered & self.candidate_positions) & !self.base_union;
+        match self.fin_rule {
+            FinRule::Unfinned => fins.is_empty() && !eliminations.is_empty(),
+            FinRule::Finned => !fins.is_empty() && self.grid.common_neighbours(&fins).intersects(&eliminations),
+            FinRule::Kraken => !fins.is_empty() && fins.len() <= MAX_KRAKEN_FINS && !eliminations.is_empty(),
+        }
+    }
 }
 
 fn fish_name<'a>(degree: usize) -> &'a str {
@@ -100,3 +196,69 @@ fn fish_name<'a>(degree: usize) -> &'a str {
         _ => "Fish",
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_finned_and_sashimi_fish_are_classified() {
+        let mut grid = Grid::<9>::from_empty_grid_and_string(
+            Grid::empty_classic(), ".45...8..2......64.7...1..2.56..9..192.6..54.....1....8..1.39.......5....3..9...8"
+        ).expect("Failed to parse grid");
+        solve(&mut grid, SINGLES);
+
+        let finned = "Finned X-Wing; on value 1 with base (Column 1, Column 8), cover (Row 1, Row 8) and fins (r9c8)";
+        let sashimi = "Sashimi X-Wing; on value 1 with base (Row 1, Row 9), cover (Column 1, Column 8) and fins (r9c7)";
+        for (strategy, description, eliminations, other) in [
+            (Strategy::FinnedFish(2), finned, ["1 eliminated from r8c7"], sashimi.replace("Sashimi", "Finned")),
+            (Strategy::SashimiFish(2), sashimi, ["1 eliminated from r8c8"], finned.replace("Finned", "Sashimi")),
+        ] {
+            let steps: Vec<_> = strategy.find_steps(&grid).filter(|step| !step.deductions(&grid).is_empty()).collect();
+            assert_eq!(steps[0].description(&grid), description);
+            assert_eq!(steps[0].deductions(&grid).iter().map(|deduction| deduction.description(&grid)).collect::<Vec<_>>(), eliminations);
+            assert!(steps.iter().all(|step| step.description(&grid) != other));
+        }
+    }
+
+    #[test]
+    fn test_franken_fish() {
+        assert_deductions(Strategy::FrankenFish(2), &[
+            ("..8.9..6.45..6......9...17.68...........837.4.....7..98.2......7..2.9.1..4.......", &["1 eliminated from r6c2"]),
+        ]);
+        assert_deductions(Strategy::FrankenFish(3), &[
+            ("....14..5.5.7....3.9..3.74...716.......4.8.1..28.5......9.4......48.1672...3.....", &["1 eliminated from r9c3"]),
+        ]);
+    }
+
+    #[test]
+    fn test_finned_franken_fish() {
+        assert_deductions(Strategy::FinnedFrankenFish(2), &[
+            (".735.1..........156.......3....8..3.3.......2..9.6.4...81.4.....5.....9.4..8....7", &["1 eliminated from r4c4", "1 eliminated from r6c4"]),
+            ("....14..5.5.7....3.9..3.74...716.......4.8.1..28.5......9.4......48.1672...3.....", &["1 eliminated from r9c3"]),
+        ]);
+    }
+
+    #[test]
+    fn test_mutant_fish() {
+        assert_deductions(Strategy::MutantFish(2), &[
+            ("....14..5.5.7....3.9..3.74...716.......4.8.1..28.5......9.4......48.1672...3.....", &["1 eliminated from r9c3"]),
+        ]);
+        assert_deductions(Strategy::MutantFish(3), &[
+            ("....14..5.5.7....3.9..3.74...716.......4.8.1..28.5......9.4......48.1672...3.....", &["1 eliminated from r9c3"]),
+        ]);
+    }
+
+    #[test]
+    fn test_finned_mutant_fish() {
+        assert_deductions(Strategy::FinnedMutantFish(2), &[
+            (".735.1..........156.......3....8..3.3.......2..9.6.4...81.4.....5.....9.4..8....7", &["1 eliminated from r6c4"]),
+            ("6.8.....427..3..6......9.2...6.98.....4.....253...41...8.......1.3.7..........6.3", &["1 eliminated from r7c5"]),
+        ]);
+    }
+}
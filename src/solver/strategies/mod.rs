@@ -2,22 +2,36 @@ mod full_house;
 mod hidden_single;
 mod naked_single;
 mod pointing_claiming;
+mod sue_de_coq;
+mod symmetrical_placement;
 mod hidden_subset;
 mod naked_subset;
 mod fish;
 mod turbot;
+mod colouring;
 mod y_wing;
 mod w_wing;
 mod xyz_wing;
+mod wing;
+mod unique_rectangle;
+mod avoidable_rectangle;
+mod bug;
+mod remote_pairs;
+mod exocet;
+mod msls;
+mod tridagon;
+mod pattern_overlay;
+mod contradiction;
 mod chaining;
 
 use std::iter::empty;
 
-use chaining::Aic;
+use chaining::{Aic, DynamicChain};
 
+use crate::analyser::symmetry::Symmetry;
 use crate::grid::cell::{CellIdx, CellSet};
 use crate::grid::candidate::{Candidate, CandidateSet};
-use crate::grid::{Grid, House};
+use crate::grid::Grid;
 
 #[derive(Copy, Clone)]
 pub enum Deduction<const N: usize> {
@@ -43,6 +57,47 @@ pub enum TurbotFlavour {
     EmptyRectangle,
 }
 
+#[derive(Copy, Clone, PartialEq)]
+pub enum FishKind {
+    Basic,
+    Franken,
+    Mutant,
+}
+
+#[derive(Copy, Clone)]
+pub enum MedusaRule {
+    TwiceInCell,
+    TwiceInHouse,
+    TwoColoursInCell,
+    TwoColoursElsewhere,
+    TwoColoursUnitAndCell,
+    CellEmptiedByColour,
+}
+
+#[derive(Copy, Clone)]
+pub enum AvoidableRectangleFlavour {
+    Type1,
+    Type2,
+}
+
+#[derive(Clone)]
+pub enum UniqueRectangleFlavour<const N: usize> {
+    Type1,
+    Type2 { value: Candidate<N> },
+    Type3 { house: CellSet<N>, cells: CellSet<N>, values: CandidateSet<N> },
+    Type4 { house: CellSet<N>, value: Candidate<N> },
+    Type5 { value: Candidate<N> },
+    Type6 { value: Candidate<N> },
+    Hidden { value: Candidate<N> },
+}
+
+#[derive(Clone)]
+pub enum ForcingPremise<const N: usize> {
+    Cell { cell: CellIdx<N> },
+    Unit { house: CellSet<N>, value: Candidate<N> },
+    Digit { cell: CellIdx<N>, value: Candidate<N> },
+}
+
 pub enum Step<const N: usize> {
     NoCandidatesForCell { cell: CellIdx<N> },
     NoPlaceForCandidateInHouse { house: CellSet<N>, value: Candidate<N> },
@@ -50,17 +105,39 @@ pub enum Step<const N: usize> {
     HiddenSingle { house: CellSet<N>, cell: CellIdx<N>, value: Candidate<N> },
     NakedSingle { cell: CellIdx<N>, value: Candidate<N> },
     PointingClaiming { house: CellSet<N>, neighbours: CellSet<N>, value: Candidate<N> },
+    SymmetricalPlacement { symmetry: Symmetry, digits: Vec<Candidate<N>> },
     HiddenSubset { house: CellSet<N>, cells: CellSet<N>, values: CandidateSet<N> },
     NakedSubset { cells: CellSet<N>, values: CandidateSet<N> },
-    Fish { base_type: House, base: CellSet<N>, cover: CellSet<N>, fins: CellSet<N>, value: Candidate<N> },
+    Fish { kind: FishKind, base: Vec<CellSet<N>>, cover: Vec<CellSet<N>>, fins: CellSet<N>, sashimi: bool, value: Candidate<N> },
     TurbotFish { flavour: TurbotFlavour, base1: CellSet<N>, base2: CellSet<N>, cover: CellSet<N>, value: Candidate<N> },
+    SimpleColouring { value: Candidate<N>, on: CellSet<N>, off: CellSet<N> },
+    MultiColouring { value: Candidate<N>, colours1: (CellSet<N>, CellSet<N>), colours2: (CellSet<N>, CellSet<N>) },
+    Medusa { rule: MedusaRule, on: Vec<(CellIdx<N>, Candidate<N>)>, off: Vec<(CellIdx<N>, Candidate<N>)> },
     YWing { pivot: CellIdx<N>, pincer1: CellIdx<N>, pincer2: CellIdx<N>, value: Candidate<N> },
     WWing { pincer1: CellIdx<N>, pincer2: CellIdx<N>, house: CellSet<N>, covered_value: Candidate<N>, eliminated_value: Candidate<N> },
     XYZWing { pivot: CellIdx<N>, pincer1: CellIdx<N>, pincer2: CellIdx<N>, value: Candidate<N> },
+    Wing { pivot: CellIdx<N>, pincers: CellSet<N> },
+    UniqueRectangle { flavour: UniqueRectangleFlavour<N>, floor: CellSet<N>, roof: CellSet<N>, values: CandidateSet<N> },
+    AvoidableRectangle { flavour: AvoidableRectangleFlavour, corners: CellSet<N>, cells: CellSet<N>, value: Candidate<N> },
+    Bug { extras: Vec<(CellIdx<N>, Candidate<N>)> },
+    SueDeCoq { line: CellSet<N>, region: CellSet<N>, intersection: CellSet<N>, line_als: CellSet<N>, region_als: CellSet<N> },
+    AlsXz { als1: CellSet<N>, als2: CellSet<N>, restricted_commons: CandidateSet<N> },
+    AlsXyWing { pivot: CellSet<N>, als1: CellSet<N>, als2: CellSet<N>, restricted_commons: (Candidate<N>, Candidate<N>) },
+    DeathBlossom { stem: CellIdx<N>, petals: Vec<(Candidate<N>, CellSet<N>)>, value: Candidate<N> },
+    RemotePairs { values: (Candidate<N>, Candidate<N>), colours: (CellSet<N>, CellSet<N>) },
     XYChain { aic: Aic<N> },
     XChain { aic: Aic<N> },
     Aic { aic: Aic<N> },
     AlsAic { aic: Aic<N> },
+    KrakenFish { fish: Box<Step<N>>, target: (CellIdx<N>, Candidate<N>), fish_chain: Option<Aic<N>>, fin_chains: Vec<Aic<N>> },
+    Exocet { base: CellSet<N>, targets: CellSet<N>, values: CandidateSet<N> },
+    Msls { cells: CellSet<N>, covers: Vec<(Candidate<N>, Vec<CellSet<N>>)>, sk_loop: bool },
+    Tridagon { cells: CellSet<N>, values: CandidateSet<N>, guardians: CellSet<N> },
+    PatternOverlay { value: Candidate<N>, partners: Vec<Candidate<N>>, num_patterns: usize, eliminations: CellSet<N>, placements: CellSet<N> },
+    ForcingChain { premise: ForcingPremise<N>, conclusion: Deduction<N>, branches: Vec<Aic<N>> },
+    DynamicForcingChain { premise: ForcingPremise<N>, conclusion: Deduction<N>, level: usize, branches: Vec<DynamicChain<N>> },
+    Nishio { cell: CellIdx<N>, value: Candidate<N>, trace: Vec<(Step<N>, Vec<Deduction<N>>)> },
+    Contradiction { cell: CellIdx<N>, value: Candidate<N>, depth: usize, trace: Vec<(Step<N>, Vec<Deduction<N>>)> },
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -69,31 +146,75 @@ pub enum Strategy {
     HiddenSingle,
     NakedSingle,
     PointingClaiming,
+    SymmetricalPlacement,
     HiddenSubset(usize),
     NakedSubset(usize),
     Fish(usize),
     FinnedFish(usize),
+    SashimiFish(usize),
+    FrankenFish(usize),
+    FinnedFrankenFish(usize),
+    MutantFish(usize),
+    FinnedMutantFish(usize),
     Skyscraper,
     TwoStringKite,
     EmptyRectangle,
+    SimpleColouring,
+    MultiColouring,
+    Medusa,
     YWing,
     WWing,
     XYZWing,
+    Wing(usize),
+    UniqueRectangle,
+    AvoidableRectangle,
+    Bug,
+    SueDeCoq,
+    AlsXz,
+    AlsXyWing,
+    DeathBlossom,
+    RemotePairs,
     XYChain,
     XChain,
     Aic,
     AlsAic,
+    KrakenFish,
+    Exocet,
+    Msls,
+    Tridagon,
+    PatternOverlay(usize),
+    CellForcingChain,
+    UnitForcingChain,
+    DigitForcingChain,
+    DynamicForcingChain(usize),
+    Nishio,
+    Contradiction(usize, &'static [Strategy]),
 }
 
+pub const SINGLES: &[Strategy] = &[Strategy::FullHouse, Strategy::HiddenSingle, Strategy::NakedSingle];
+
 pub fn all_strategies(n: usize) -> Vec<Strategy> {
     empty()
         .chain([Strategy::FullHouse, Strategy::HiddenSingle, Strategy::NakedSingle, Strategy::PointingClaiming])
         .chain((2 ..= n / 2).flat_map(|degree| [Strategy::NakedSubset(degree), Strategy::HiddenSubset(degree)]))
+        .chain([Strategy::SymmetricalPlacement])
         .chain((2 ..= n / 2).map(|degree| Strategy::Fish(degree)))
         .chain([Strategy::Skyscraper, Strategy::TwoStringKite, Strategy::EmptyRectangle])
+        .chain([Strategy::SimpleColouring, Strategy::MultiColouring])
         .chain([Strategy::YWing, Strategy::WWing, Strategy::XYZWing])
-        .chain((2 ..= n / 2).map(|degree| Strategy::FinnedFish(degree)))
-        .chain([Strategy::XYChain, Strategy::XChain, Strategy::Aic, Strategy::AlsAic])
+        .chain((4 ..= n / 2 + 1).map(Strategy::Wing))
+        .chain([Strategy::UniqueRectangle, Strategy::AvoidableRectangle, Strategy::Bug])
+        .chain((2 ..= n / 2).flat_map(|degree| [Strategy::FinnedFish(degree), Strategy::SashimiFish(degree)]))
+        .chain([Strategy::SueDeCoq, Strategy::Medusa])
+        .chain([Strategy::AlsXz, Strategy::AlsXyWing, Strategy::DeathBlossom])
+        .chain([Strategy::RemotePairs, Strategy::XYChain, Strategy::XChain, Strategy::Aic, Strategy::AlsAic])
+        .chain((2 ..= n / 2).flat_map(|degree| [Strategy::FrankenFish(degree), Strategy::FinnedFrankenFish(degree)]))
+        .chain([Strategy::KrakenFish])
+        .chain((2 ..= n / 2).flat_map(|degree| [Strategy::MutantFish(degree), Strategy::FinnedMutantFish(degree)]))
+        .chain([Strategy::Exocet, Strategy::Msls, Strategy::Tridagon])
+        .chain([Strategy::PatternOverlay(1), Strategy::PatternOverlay(2)])
+        .chain([Strategy::CellForcingChain, Strategy::UnitForcingChain, Strategy::DigitForcingChain])
+        .chain([Strategy::Nishio, Strategy::DynamicForcingChain(0), Strategy::DynamicForcingChain(1), Strategy::Contradiction(2, SINGLES)])
         .collect()
 }
 
@@ -107,17 +228,39 @@ impl<const N: usize> Step<N> {
             ref hidden_single @ Step::HiddenSingle { .. } => hidden_single::deductions(grid, hidden_single),
             ref naked_single @ Step::NakedSingle { .. } => naked_single::deductions(grid, naked_single),
             ref pointing_claiming @ Step::PointingClaiming { .. } => pointing_claiming::deductions(grid, pointing_claiming),
+            ref symmetrical_placement @ Step::SymmetricalPlacement { .. } => symmetrical_placement::deductions(grid, symmetrical_placement),
             ref hidden_subset @ Step::HiddenSubset { .. } => hidden_subset::deductions(grid, hidden_subset),
             ref naked_subset @ Step::NakedSubset { .. } => naked_subset::deductions(grid, naked_subset),
             ref fish @ Step::Fish { .. } => fish::deductions(grid, fish),
             ref turbot_fish @ Step::TurbotFish { .. } => turbot::deductions(grid, turbot_fish),
+            ref simple_colouring @ Step::SimpleColouring { .. } => colouring::deductions(grid, simple_colouring),
+            ref multi_colouring @ Step::MultiColouring { .. } => colouring::deductions(grid, multi_colouring),
+            ref medusa @ Step::Medusa { .. } => chaining::deductions(grid, medusa),
             ref y_wing @ Step::YWing { .. } => y_wing::deductions(grid, y_wing),
             ref w_wing @ Step::WWing { .. } => w_wing::deductions(grid, w_wing),
             ref xyz_wing @ Step::XYZWing { .. } => xyz_wing::deductions(grid, xyz_wing),
+            ref wing @ Step::Wing { .. } => wing::deductions(grid, wing),
+            ref unique_rectangle @ Step::UniqueRectangle { .. } => unique_rectangle::deductions(grid, unique_rectangle),
+            ref avoidable_rectangle @ Step::AvoidableRectangle { .. } => avoidable_rectangle::deductions(grid, avoidable_rectangle),
+            ref bug @ Step::Bug { .. } => bug::deductions(grid, bug),
+            ref sue_de_coq @ Step::SueDeCoq { .. } => sue_de_coq::deductions(grid, sue_de_coq),
+            ref als_xz @ Step::AlsXz { .. } => chaining::deductions(grid, als_xz),
+            ref als_xy_wing @ Step::AlsXyWing { .. } => chaining::deductions(grid, als_xy_wing),
+            ref death_blossom @ Step::DeathBlossom { .. } => chaining::deductions(grid, death_blossom),
+            ref remote_pairs @ Step::RemotePairs { .. } => remote_pairs::deductions(grid, remote_pairs),
             ref xy_chain @ Step::XYChain { .. } => chaining::deductions(grid, xy_chain),
             ref x_chain @ Step::XChain { .. } => chaining::deductions(grid, x_chain),
             ref aic @ Step::Aic { .. } => chaining::deductions(grid, aic),
             ref als_aic @ Step::AlsAic { .. } => chaining::deductions(grid, als_aic),
+            ref kraken_fish @ Step::KrakenFish { .. } => chaining::deductions(grid, kraken_fish),
+            ref exocet @ Step::Exocet { .. } => exocet::deductions(grid, exocet),
+            ref msls @ Step::Msls { .. } => msls::deductions(grid, msls),
+            ref tridagon @ Step::Tridagon { .. } => tridagon::deductions(grid, tridagon),
+            ref pattern_overlay @ Step::PatternOverlay { .. } => pattern_overlay::deductions(grid, pattern_overlay),
+            ref forcing_chain @ Step::ForcingChain { .. } => chaining::deductions(grid, forcing_chain),
+            ref dynamic_chain @ Step::DynamicForcingChain { .. } => chaining::deductions(grid, dynamic_chain),
+            ref nishio @ Step::Nishio { .. } => contradiction::deductions(grid, nishio),
+            ref contradiction @ Step::Contradiction { .. } => contradiction::deductions(grid, contradiction),
         }
     }
 
@@ -129,17 +272,39 @@ impl<const N: usize> Step<N> {
             ref hidden_single @ Step::HiddenSingle { .. } => hidden_single::description(grid, hidden_single),
             ref naked_single @ Step::NakedSingle { .. } => naked_single::description(grid, naked_single),
             ref pointing_claiming @ Step::PointingClaiming { .. } => pointing_claiming::description(grid, pointing_claiming),
+            ref symmetrical_placement @ Step::SymmetricalPlacement { .. } => symmetrical_placement::description(grid, symmetrical_placement),
             ref hidden_subset @ Step::HiddenSubset { .. } => hidden_subset::description(grid, hidden_subset),
             ref naked_subset @ Step::NakedSubset { .. } => naked_subset::description(grid, naked_subset),
             ref fish @ Step::Fish { .. } => fish::description(grid, fish),
             ref turbot_fish @ Step::TurbotFish { .. } => turbot::description(grid, turbot_fish),
+            ref simple_colouring @ Step::SimpleColouring { .. } => colouring::description(grid, simple_colouring),
+            ref multi_colouring @ Step::MultiColouring { .. } => colouring::description(grid, multi_colouring),
+            ref medusa @ Step::Medusa { .. } => chaining::description(grid, medusa),
             ref y_wing @ Step::YWing { .. } => y_wing::description(grid, y_wing),
             ref w_wing @ Step::WWing { .. } => w_wing::description(grid, w_wing),
             ref xyz_wing @ Step::XYZWing { .. } => xyz_wing::description(grid, xyz_wing),
+            ref wing @ Step::Wing { .. } => wing::description(grid, wing),
+            ref unique_rectangle @ Step::UniqueRectangle { .. } => unique_rectangle::description(grid, unique_rectangle),
+            ref avoidable_rectangle @ Step::AvoidableRectangle { .. } => avoidable_rectangle::description(grid, avoidable_rectangle),
+            ref bug @ Step::Bug { .. } => bug::description(grid, bug),
+            ref sue_de_coq @ Step::SueDeCoq { .. } => sue_de_coq::description(grid, sue_de_coq),
+            ref als_xz @ Step::AlsXz { .. } => chaining::description(grid, als_xz),
+            ref als_xy_wing @ Step::AlsXyWing { .. } => chaining::description(grid, als_xy_wing),
+            ref death_blossom @ Step::DeathBlossom { .. } => chaining::description(grid, death_blossom),
+            ref remote_pairs @ Step::RemotePairs { .. } => remote_pairs::description(grid, remote_pairs),
             ref xy_chain @ Step::XYChain { .. } => chaining::description(grid, xy_chain),
             ref x_chain @ Step::XChain { .. } => chaining::description(grid, x_chain),
             ref aic @ Step::Aic { .. } => chaining::description(grid, aic),
             ref als_aic @ Step::AlsAic { .. } => chaining::description(grid, als_aic),
+            ref kraken_fish @ Step::KrakenFish { .. } => chaining::description(grid, kraken_fish),
+            ref exocet @ Step::Exocet { .. } => exocet::description(grid, exocet),
+            ref msls @ Step::Msls { .. } => msls::description(grid, msls),
+            ref tridagon @ Step::Tridagon { .. } => tridagon::description(grid, tridagon),
+            ref pattern_overlay @ Step::PatternOverlay { .. } => pattern_overlay::description(grid, pattern_overlay),
+            ref forcing_chain @ Step::ForcingChain { .. } => chaining::description(grid, forcing_chain),
+            ref dynamic_chain @ Step::DynamicForcingChain { .. } => chaining::description(grid, dynamic_chain),
+            ref nishio @ Step::Nishio { .. } => contradiction::description(grid, nishio),
+            ref contradiction @ Step::Contradiction { .. } => contradiction::description(grid, contradiction),
         }
     }
 }
@@ -151,20 +316,81 @@ impl Strategy {
             Strategy::HiddenSingle => Box::new(hidden_single::find(grid)),
             Strategy::NakedSingle => Box::new(naked_single::find(grid)),
             Strategy::PointingClaiming => Box::new(pointing_claiming::find(grid)),
+            Strategy::SymmetricalPlacement => Box::new(symmetrical_placement::find(grid)),
             Strategy::HiddenSubset(degree) => Box::new(hidden_subset::find(grid, degree)),
             Strategy::NakedSubset(degree) => Box::new(naked_subset::find(grid, degree)),
-            Strategy::Fish(degree) => Box::new(fish::find(grid, degree, false)),
-            Strategy::FinnedFish(degree) => Box::new(fish::find(grid, degree, true)),
+            Strategy::Fish(degree) => Box::new(fish::find(grid, FishKind::Basic, degree, false)),
+            Strategy::FinnedFish(degree) => Box::new(fish::find_finned(grid, FishKind::Basic, degree, false)),
+            Strategy::SashimiFish(degree) => Box::new(fish::find_finned(grid, FishKind::Basic, degree, true)),
+            Strategy::FrankenFish(degree) => Box::new(fish::find(grid, FishKind::Franken, degree, false)),
+            Strategy::FinnedFrankenFish(degree) => Box::new(fish::find(grid, FishKind::Franken, degree, true)),
+            Strategy::MutantFish(degree) => Box::new(fish::find(grid, FishKind::Mutant, degree, false)),
+            Strategy::FinnedMutantFish(degree) => Box::new(fish::find(grid, FishKind::Mutant, degree, true)),
             Strategy::Skyscraper => Box::new(turbot::find_skyscrapers(grid)),
             Strategy::TwoStringKite => Box::new(turbot::find_kites(grid)),
             Strategy::EmptyRectangle => Box::new(turbot::find_rectangles(grid)),
+            Strategy::SimpleColouring => Box::new(colouring::find_simple_colourings(grid)),
+            Strategy::MultiColouring => Box::new(colouring::find_multi_colourings(grid)),
+            Strategy::Medusa => Box::new(chaining::find_medusas(grid)),
             Strategy::YWing => Box::new(y_wing::find(grid)),
             Strategy::WWing => Box::new(w_wing::find(grid)),
             Strategy::XYZWing => Box::new(xyz_wing::find(grid)),
+            Strategy::Wing(size) => Box::new(wing::find(grid, size)),
+            Strategy::UniqueRectangle => Box::new(unique_rectangle::find(grid)),
+            Strategy::AvoidableRectangle => Box::new(avoidable_rectangle::find(grid)),
+            Strategy::Bug => Box::new(bug::find(grid)),
+            Strategy::SueDeCoq => Box::new(sue_de_coq::find(grid)),
+            Strategy::AlsXz => Box::new(chaining::find_als_xzs(grid)),
+            Strategy::AlsXyWing => Box::new(chaining::find_als_xy_wings(grid)),
+            Strategy::DeathBlossom => Box::new(chaining::find_death_blossoms(grid)),
+            Strategy::RemotePairs => Box::new(remote_pairs::find(grid)),
             Strategy::XYChain => Box::new(chaining::find_xy_chains(grid)),
             Strategy::XChain => Box::new(chaining::find_x_chains(grid)),
             Strategy::Aic => Box::new(chaining::find_aics(grid)),
             Strategy::AlsAic => Box::new(chaining::find_als_aics(grid)),
+            Strategy::KrakenFish => Box::new(chaining::find_kraken_fish(grid)),
+            Strategy::Exocet => Box::new(exocet::find(grid)),
+            Strategy::Msls => Box::new(msls::find(grid)),
+            Strategy::Tridagon => Box::new(tridagon::find(grid)),
+            Strategy::PatternOverlay(degree) => Box::new(pattern_overlay::find(grid, degree)),
+            Strategy::CellForcingChain => Box::new(chaining::find_cell_forcing_chains(grid)),
+            Strategy::UnitForcingChain => Box::new(chaining::find_unit_forcing_chains(grid)),
+            Strategy::DigitForcingChain => Box::new(chaining::find_digit_forcing_chains(grid)),
+            Strategy::DynamicForcingChain(level) => Box::new(chaining::find_dynamic_forcing_chains(grid, level)),
+            Strategy::Nishio => Box::new(contradiction::find_nishios(grid)),
+            Strategy::Contradiction(depth, strategies) => Box::new(contradiction::find(grid, depth, strategies)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+
+    use crate::generator::brute_force::BruteForceSolver;
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+
+    use super::{Deduction, Strategy, SINGLES};
+
+    pub fn assert_deductions(strategy: Strategy, puzzles: &[(&str, &[&str])]) {
+        for &(puzzle, expected) in puzzles {
+            let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+            let solution = BruteForceSolver::for_starting_grid(&grid).solution(&[0; 81]);
+            grid.declare_unique_solution();
+            solve(&mut grid, SINGLES);
+
+            let deductions = strategy.find_steps(&grid).map(|step| step.deductions(&grid)).find(|deductions| !deductions.is_empty())
+                .unwrap_or_else(|| panic!("No {:?} step found in {}", strategy, puzzle));
+            for &deduction in &deductions {
+                match deduction {
+                    Deduction::Placement(cell, value) => assert_eq!(value, Candidate(solution[cell.0])),
+                    Deduction::Elimination(cell, value) => assert_ne!(value, Candidate(solution[cell.0])),
+                    Deduction::Contradiction => panic!("Unexpected contradiction"),
+                }
+            }
+            assert_eq!(deductions.iter().map(|deduction| deduction.description(&grid)).collect::<Vec<_>>(), expected);
         }
     }
 }
@@ -0,0 +1,145 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::CellSet;
+
+use super::{Deduction, Step};
+
+type Covers<const N: usize> = Vec<(Candidate<N>, Vec<CellSet<N>>)>;
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let line_sets = |lines: &'a [CellSet<N>]| -> Vec<(Vec<&'a CellSet<N>>, CellSet<N>)> {
+        (2 ..= N / 2).flat_map(|size| lines.iter().combinations(size).map(|lines| {
+            let union = CellSet::union(lines.iter().copied().cloned());
+            (lines, union)
+        })).collect()
+    };
+    let (row_sets, column_sets) = (line_sets(grid.rows()), line_sets(grid.columns()));
+    let empty_cells = grid.empty_cells();
+    let value_cells: Vec<_> = grid.all_values().iter().map(|value| (value, grid.cells_with_candidate(value))).collect();
+    let regions: Vec<_> = grid.regions().iter().collect();
+
+    let intersections = row_sets.into_iter().cartesian_product(column_sets)
+        .filter_map(move |((rows, row_cells), (columns, column_cells))| {
+            let cells = &(&row_cells & &column_cells) & &empty_cells;
+            if cells.len() < 4 { return None; }
+            let covers = sector_covers(&cells, &value_cells, [&rows, &columns, &regions])?;
+            Some(Step::Msls { cells, covers, sk_loop: false })
+        });
+    let sk_loops = grid.rows().iter().tuple_combinations().cartesian_product(grid.columns().iter().tuple_combinations().collect::<Vec<_>>())
+        .filter_map(move |((row1, row2), (column1, column2))| {
+            let corners = [row1 & column1, row1 & column2, row2 & column1, row2 & column2];
+            let regions: Vec<_> = corners.iter().filter_map(|corner| grid.regions().iter().find(|region| region.contains_all(corner))).unique_by(|region| region.first()).collect();
+            if regions.len() != 4 { return None; }
+            let lines = CellSet::union([row1, row2, column1, column2].into_iter().cloned());
+            let cells = grid.empty_cells_in(&(&lines & &CellSet::union(regions.into_iter().cloned())));
+            let covers = mixed_covers(grid, &cells)?;
+            Some(Step::Msls { cells, covers, sk_loop: true })
+        });
+
+    intersections.chain(sk_loops)
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, msls: &Step<N>) -> Vec<Deduction<N>> {
+    match msls {
+        Step::Msls { cells, covers, .. } => covers.iter()
+            .flat_map(|(value, houses)| {
+                let elimination_cells = grid.cells_with_candidate_in(&(&CellSet::union(houses.iter().cloned()) & !cells), *value);
+                elimination_cells.iter().map(|cell| Deduction::Elimination(cell, *value)).collect::<Vec<_>>()
+            })
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, msls: &Step<N>) -> String {
+    match msls {
+        Step::Msls { cells, covers, sk_loop } => format!(
+            "{}; the {} cells {} are filled by {}",
+            if *sk_loop { "SK-Loop" } else { "Multi-Sector Locked Set" }, cells.len(), grid.cell_set_name(cells),
+            covers.iter().map(|(value, houses)| format!("{} once in each of {}", value.0, houses.iter().map(|house| grid.cell_set_name(house)).join(", "))).join("; ")
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn sector_covers<const N: usize>(cells: &CellSet<N>, value_cells: &[(Candidate<N>, CellSet<N>)], sectors: [&[&CellSet<N>]; 3]) -> Option<Covers<N>> {
+    let mut covers = Vec::new();
+    let mut num_covers = 0;
+    for (value, value_cells) in value_cells.iter().map(|(value, value_cells)| (value, value_cells & cells)).filter(|(_, value_cells)| !value_cells.is_empty()) {
+        let cover = sectors.iter()
+            .map(|houses| value_cells.iter().filter_map(|cell| houses.iter().position(|house| house.contains(cell))).unique().map(|idx| houses[idx]).collect::<Vec<_>>())
+            .min_by_key(Vec::len)
+            .unwrap();
+        num_covers += cover.len();
+        if num_covers > cells.len() { return None; }
+        covers.push((*value, cover));
+    }
+    (num_covers == cells.len()).then(|| covers.into_iter().map(|(value, cover)| (value, cover.into_iter().cloned().collect())).collect())
+}
+
+fn mixed_covers<const N: usize>(grid: &Grid<N>, cells: &CellSet<N>) -> Option<Covers<N>> {
+    let mut covers = Vec::new();
+    let mut num_covers = 0;
+    let values = grid.candidates_in(cells);
+    if values.len() > cells.len() { return None; }
+    for (idx, value) in values.iter().enumerate() {
+        let value_cells = grid.cells_with_candidate_in(cells, value);
+        let budget = cells.len() - num_covers - (values.len() - idx - 1);
+        let cover = (1 ..= budget).find_map(|size| minimal_cover(grid, &value_cells, size))?;
+        num_covers += cover.len();
+        covers.push((value, cover));
+    }
+    (num_covers == cells.len()).then(|| covers.into_iter().map(|(value, cover)| (value, cover.into_iter().cloned().collect())).collect())
+}
+
+fn minimal_cover<'a, const N: usize>(grid: &'a Grid<N>, cells: &CellSet<N>, size: usize) -> Option<Vec<&'a CellSet<N>>> {
+    let Some(cell) = cells.first() else { return Some(vec![]) };
+    if size == 0 { return None; }
+    grid.all_houses().iter()
+        .filter(|house| house.contains(cell))
+        .find_map(|house| {
+            let mut cover = minimal_cover(grid, &(cells & !house), size - 1)?;
+            cover.push(house);
+            Some(cover)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_msls() {
+        assert_deductions(Strategy::Msls, &[
+            ("5.....8...6.2..5..4.2.85..384...1...39..5..6.......3142..3....6....2.......9.6.8.", &[
+                "1 eliminated from r8c9", "1 eliminated from r9c9", "4 eliminated from r7c8",
+            ]),
+            ("1..8..4.73....1...874..2..1..97..2......2..5..6....9......6.8..6.89...4......5..3", &["9 eliminated from r7c2", "9 eliminated from r9c2"]),
+            ("...3.....4...51.....2.7..1..6...71...2......6..892...3..6...3...8...9......4..6.8", &["5 eliminated from r1c7", "5 eliminated from r1c9"]),
+        ]);
+    }
+
+    #[test]
+    fn test_msls_with_box_covers() {
+        let mut grid = Grid::<9>::from_empty_grid_and_string(
+            Grid::empty_classic(), "1..8..4.73....1...874..2..1..97..2......2..5..6....9......6.8..6.89...4......5..3"
+        ).expect("Failed to parse grid");
+        solve(&mut grid, SINGLES);
+
+        let step = Strategy::Msls.find_steps(&grid).find(|step| !step.deductions(&grid).is_empty()).expect("No MSLS found");
+        assert_eq!(
+            step.description(&grid),
+            "Multi-Sector Locked Set; the 4 cells (r1c2, r1c3, r2c2, r2c3) are filled by 2 once in each of Region 1; 5 once in each of Region 1; \
+             6 once in each of Column 3; 9 once in each of Column 2"
+        );
+        assert_eq!(step.deductions(&grid).iter().map(|deduction| deduction.description(&grid)).collect::<Vec<_>>(), ["9 eliminated from r7c2", "9 eliminated from r9c2"]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::iter::once;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::Candidate;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>, degree: usize) -> impl Iterator<Item = Step<N>> + 'a {
+    let templates: Vec<_> = if degree == 0 { vec![] } else {
+        grid.all_values().iter()
+            .filter(|&value| !grid.cells_with_candidate(value).is_empty())
+            .map(|value| (value, templates(grid, value)))
+            .collect()
+    };
+
+    (0 .. templates.len()).flat_map(move |idx| {
+        let (value, value_templates) = &templates[idx];
+        let candidate_cells = grid.cells_with_candidate(*value);
+        templates.iter().enumerate()
+            .filter(|&(other, _)| other != idx)
+            .map(|(_, partner)| partner)
+            .combinations(degree - 1)
+            .filter_map(|partners| {
+                let surviving = surviving_templates(value_templates, partners.iter().map(|(_, partner_templates)| partner_templates.as_slice()));
+                let eliminations = &candidate_cells & !&CellSet::union(surviving.iter().copied());
+                let placements = surviving.iter().copied().cloned().reduce(|common, template| &common & &template)
+                    .map_or_else(CellSet::empty, |common| &common & &candidate_cells);
+                (!eliminations.is_empty() || !placements.is_empty()).then(|| Step::PatternOverlay {
+                    value: *value,
+                    partners: partners.iter().map(|(partner, _)| *partner).collect(),
+                    num_patterns: surviving.len(),
+                    eliminations,
+                    placements,
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+pub fn deductions<const N: usize>(_grid: &Grid<N>, pattern_overlay: &Step<N>) -> Vec<Deduction<N>> {
+    match pattern_overlay {
+        Step::PatternOverlay { value, eliminations, placements, .. } => placements.iter().map(|cell| Deduction::Placement(cell, *value))
+            .chain(eliminations.iter().map(|cell| Deduction::Elimination(cell, *value)))
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, pattern_overlay: &Step<N>) -> String {
+    match pattern_overlay {
+        Step::PatternOverlay { value, partners, num_patterns, eliminations, placements } => format!(
+            "Pattern Overlay; of the {} patterns for {}{}, {}{}{}",
+            num_patterns, value.0,
+            if partners.is_empty() { String::new() } else { format!(" compatible with {}", partners.iter().map(|partner| partner.0).join(", ")) },
+            if placements.is_empty() { String::new() } else { format!("all use {}", grid.cell_set_name(placements)) },
+            if placements.is_empty() || eliminations.is_empty() { "" } else { " and " },
+            if eliminations.is_empty() { String::new() } else { format!("none use {}", grid.cell_set_name(eliminations)) }
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn templates<const N: usize>(grid: &Grid<N>, value: Candidate<N>) -> Vec<CellSet<N>> {
+    let mut templates = Vec::new();
+    extend_template(grid, value, &mut vec![], &mut templates);
+    templates
+}
+
+fn extend_template<const N: usize>(grid: &Grid<N>, value: Candidate<N>, template: &mut Vec<CellIdx<N>>, templates: &mut Vec<CellSet<N>>) {
+    let Some(row) = grid.rows().get(template.len()) else {
+        let cells = CellSet::from_cells(template.iter().copied());
+        if grid.all_houses().iter().all(|house| house.iter().any(|cell| cells.contains(cell))) {
+            templates.push(cells);
+        }
+        return;
+    };
+    let placed = row.iter().find(|&cell| grid.value(cell) == Some(value));
+    for cell in placed.map_or_else(|| grid.cells_with_candidate_in(row, value), |cell| CellSet::from_cells([cell])).iter() {
+        if template.iter().any(|&other| grid.neighbours(cell).contains(other)) { continue; }
+        template.push(cell);
+        extend_template(grid, value, template, templates);
+        template.pop();
+    }
+}
+
+fn surviving_templates<'a, const N: usize>(templates: &'a [CellSet<N>], partner_templates: impl Iterator<Item = &'a [CellSet<N>]>) -> Vec<&'a CellSet<N>> {
+    let mut template_lists: Vec<Vec<_>> = once(templates).chain(partner_templates).map(|list| list.iter().collect()).collect();
+    loop {
+        if template_lists.iter().any(Vec::is_empty) { return vec![]; }
+        let forced_cells: Vec<_> = template_lists.iter().map(|list| CellSet::intersection(list.iter().copied())).collect();
+        let mut pruned = false;
+        for (idx, list) in template_lists.iter_mut().enumerate() {
+            let blocked = CellSet::union(forced_cells.iter().enumerate().filter(|&(other, _)| other != idx).map(|(_, cells)| cells));
+            let num_templates = list.len();
+            list.retain(|template| !template.intersects(&blocked));
+            pruned |= list.len() < num_templates;
+        }
+        if !pruned { break; }
+    }
+
+    let (value_templates, partner_lists) = template_lists.split_first().unwrap();
+    value_templates.iter().copied().filter(|template| compatible(template, partner_lists)).collect()
+}
+
+fn compatible<const N: usize>(template: &CellSet<N>, partner_lists: &[Vec<&CellSet<N>>]) -> bool {
+    let Some((first, rest)) = partner_lists.split_first() else { return true };
+    first.iter()
+        .filter(|partner| partner.iter().all(|cell| !template.contains(cell)))
+        .any(|&partner| compatible(&(template | partner), rest))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::{HashMap, HashSet};
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{Deduction, Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_pattern_overlay() {
+        assert_deductions(Strategy::PatternOverlay(1), &[
+            ("....7.943..25..1..7......5.84...62.....9..7....54...6..58..4..1...6..........5..7", &["1 eliminated from r3c2"]),
+            (".6..89....15..........5...93...2.5.4.78...9.....4..8.....5.......63....7..3..6.1.", &["1 eliminated from r1c9"]),
+            ("1...6...8..51.....79.2...6..3...8.9.......1.5.....6........13..2..5..9..9.......6", &[
+                "2 placed in r2c7", "2 eliminated from r1c7", "2 eliminated from r2c8",
+            ]),
+            ("...3....774....2.6..1.......8....67......1...1....5.29.73..8.....9.6...5...23....", &[
+                "2 placed in r3c6", "2 eliminated from r1c6", "2 eliminated from r3c5",
+            ]),
+        ]);
+        assert_deductions(Strategy::PatternOverlay(2), &[
+            ("..9.7.3.51....42...4..8.......6...5.......7...2...7.1......6..1.3..4..8.6.45....3", &[
+                "2 eliminated from r7c1", "2 eliminated from r7c3", "2 eliminated from r8c4",
+            ]),
+            (".5.7....4....98.......3..95.3..5...27...........8..6......139..4.862..3.2.......7", &["7 eliminated from r2c2"]),
+            (".79.........8.7..34..5...8......8.4....36..1..25............3.8.3......9.1...5...", &["6 eliminated from r8c1", "6 eliminated from r8c8"]),
+        ]);
+    }
+
+    #[test]
+    fn test_pattern_overlay_of_degree_zero_finds_nothing() {
+        let grid = Grid::<9>::empty_classic();
+        assert_eq!(Strategy::PatternOverlay(0).find_steps(&grid).count(), 0);
+    }
+
+    #[test]
+    fn test_pattern_overlay_subsumes_single_digit_strategies() {
+        let puzzles = [
+            (Strategy::PointingClaiming, "....6.71.8.15..9......4.............1.7...4299.3.2......9..3..2..24.61.....1....3"),
+            (Strategy::Fish(2), ".735.1..........156.......3....8..3.3.......2..9.6.4...81.4.....5.....9.4..8....7"),
+            (Strategy::Fish(3), ".......43.7268......51....7.1.34......9.......2.79...1.6......5..1....6.8.4..5..."),
+            (Strategy::FinnedFish(2), "......593.2...3..18........2...4..5......28..4...7.31....8.91....31...7..457....."),
+            (Strategy::SashimiFish(2), ".45...8..2......64.7...1..2.56..9..192.6..54.....1....8..1.39.......5....3..9...8"),
+            (Strategy::Skyscraper, ".........3...58.....63..15...47...3.....698..5.....27.....1..2.67.......48..95..."),
+            (Strategy::TwoStringKite, ".........3...58.....63..15...47...3.....698..5.....27.....1..2.67.......48..95..."),
+            (Strategy::EmptyRectangle, ".45...8..2......64.7...1..2.56..9..192.6..54.....1....8..1.39.......5....3..9...8"),
+            (Strategy::SimpleColouring, "........1....23.4.....452....1.....6.3...6.2.6.7...8....8.5.....5...4...1..6....9"),
+            (Strategy::MultiColouring, "......593.2...3..18........2...4..5......28..4...7.31....8.91....31...7..457....."),
+            (Strategy::XChain, "3.4.........3....621.4.........82.5.8.....7........16..5..9..1....2...7..8..672.4"),
+        ];
+        for (strategy, puzzle) in puzzles {
+            let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+            solve(&mut grid, SINGLES);
+
+            let overlay_deductions: Vec<_> = Strategy::PatternOverlay(1).find_steps(&grid).flat_map(|step| step.deductions(&grid)).collect();
+            let overlay_eliminations: HashSet<_> = overlay_deductions.iter()
+                .filter_map(|deduction| match deduction {
+                    Deduction::Elimination(cell, value) => Some((cell.0, value.0)),
+                    _ => None,
+                })
+                .collect();
+            let overlay_placements: HashMap<_, _> = overlay_deductions.iter()
+                .filter_map(|deduction| match deduction {
+                    Deduction::Placement(cell, value) => Some((cell.0, value.0)),
+                    _ => None,
+                })
+                .collect();
+            let deductions: Vec<_> = strategy.find_steps(&grid).flat_map(|step| step.deductions(&grid)).collect();
+            assert!(!deductions.is_empty(), "No {:?} step found in {}", strategy, puzzle);
+            for deduction in deductions {
+                match deduction {
+                    Deduction::Elimination(cell, value) => assert!(
+                        overlay_eliminations.contains(&(cell.0, value.0)) || overlay_placements.get(&cell.0).is_some_and(|&placed| placed != value.0)
+                    ),
+                    _ => panic!("Unexpected deduction"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    grid.all_values().iter().tuple_combinations().collect::<Vec<_>>().into_iter()
+        .flat_map(move |(value1, value2)| {
+            let cells: Vec<_> = grid.cells_with_n_candidates(2).iter()
+                .filter(|&cell| grid.has_candidate(cell, value1) && grid.has_candidate(cell, value2))
+                .collect();
+            chains(grid, &cells).into_iter().map(move |colours| Step::RemotePairs { values: (value1, value2), colours })
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, remote_pairs: &Step<N>) -> Vec<Deduction<N>> {
+    match remote_pairs {
+        Step::RemotePairs { values: (value1, value2), colours: (colour1, colour2) } => {
+            let elimination_cells = &(&grid.seen_by(colour1) & &grid.seen_by(colour2)) & !(colour1 | colour2);
+            elimination_cells.iter()
+                .flat_map(|cell| [*value1, *value2].into_iter().filter(move |&value| grid.has_candidate(cell, value)).map(move |value| Deduction::Elimination(cell, value)))
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, remote_pairs: &Step<N>) -> String {
+    match remote_pairs {
+        Step::RemotePairs { values: (value1, value2), colours: (colour1, colour2) } => format!(
+            "Remote Pairs; {}{} coloured {} / {}, so cells seeing both colours cannot contain {} or {}",
+            value1.0, value2.0, grid.cell_set_name(colour1), grid.cell_set_name(colour2), value1.0, value2.0
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn chains<const N: usize>(grid: &Grid<N>, cells: &[CellIdx<N>]) -> Vec<(CellSet<N>, CellSet<N>)> {
+    let mut uncoloured = CellSet::from_cells(cells.iter().copied());
+    let mut chains = Vec::new();
+    while let Some(start) = uncoloured.first() {
+        let mut colours = [CellSet::from_cells([start]), CellSet::empty()];
+        let mut queue = vec![(start, 0)];
+        uncoloured.remove_cell(start);
+
+        while let Some((cell, colour)) = queue.pop() {
+            for other in (grid.neighbours(cell) & &uncoloured).iter() {
+                uncoloured.remove_cell(other);
+                colours[1 - colour].add_cell(other);
+                queue.push((other, 1 - colour));
+            }
+        }
+
+        let [colour1, colour2] = colours;
+        if colour1.len() + colour2.len() > 3 && !grid.sees_any(&colour1, &colour1) && !grid.sees_any(&colour2, &colour2) {
+            chains.push((colour1, colour2));
+        }
+    }
+
+    chains
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_remote_pairs() {
+        assert_deductions(Strategy::RemotePairs, &[
+            ("6.8.....427..3..6......9.2...6.98.....4.....253...41...8.......1.3.7..........6.3", &[
+                "5 eliminated from r2c7", "1 eliminated from r2c9", "5 eliminated from r3c4", "1 eliminated from r3c5", "5 eliminated from r3c5",
+            ]),
+            ("............84.5....4296....8...7.9..7.5.1..8..6........23..9...6.....2.5.39..6..", &[
+                "1 eliminated from r1c1", "7 eliminated from r1c1", "1 eliminated from r1c2", "1 eliminated from r1c3", "7 eliminated from r1c3",
+                "1 eliminated from r1c7", "7 eliminated from r1c7", "1 eliminated from r1c8", "7 eliminated from r1c8", "1 eliminated from r1c9",
+                "7 eliminated from r1c9",
+            ]),
+            (".......9..5.6.4.8.48....25....9..82....3.....2.6.....57....64.....4.5....6...8..9", &[
+                "1 eliminated from r2c3", "7 eliminated from r2c3", "1 eliminated from r5c2", "7 eliminated from r5c2", "1 eliminated from r8c3",
+            ]),
+        ]);
+    }
+}
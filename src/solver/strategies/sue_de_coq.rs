@@ -0,0 +1,92 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::CandidateSet;
+use crate::grid::cell::CellSet;
+
+use super::chaining::almost_locked_sets;
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let sets = almost_locked_sets(grid);
+    grid.rows().iter().chain(grid.columns())
+        .cartesian_product(grid.regions())
+        .filter(move |(line, region)| grid.empty_cells_in(&(*line & *region)).len() >= 2)
+        .flat_map(move |(line, region)| {
+            let empty_cells = grid.empty_cells_in(&(line & region));
+            let (line_sets, region_sets): (Vec<_>, Vec<_>) = (
+                sets.iter().filter(|(cells, _)| (line & !region).contains_all(cells)).collect(),
+                sets.iter().filter(|(cells, _)| (region & !line).contains_all(cells)).collect(),
+            );
+            (2 ..= empty_cells.len())
+                .flat_map(|degree| empty_cells.iter().combinations(degree).map(CellSet::from_cells))
+                .flat_map(|intersection| {
+                    let values = grid.candidates_in(&intersection);
+                    if values.len() < intersection.len() + 2 { return vec![]; }
+                    line_sets.iter().cartesian_product(region_sets.iter())
+                        .filter(|((_, line_values), (_, region_values))| {
+                            line_values.intersects(&values) && region_values.intersects(&values) && !line_values.intersects(region_values)
+                        })
+                        .filter(|((line_als, line_values), (region_als, region_values))| {
+                            CandidateSet::union([&values, line_values, region_values]).len() == intersection.len() + line_als.len() + region_als.len()
+                        })
+                        .map(|((line_als, _), (region_als, _))| Step::SueDeCoq {
+                            line: line.clone(), region: region.clone(), intersection: intersection.clone(), line_als: line_als.clone(), region_als: region_als.clone(),
+                        })
+                        .collect()
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, sue_de_coq: &Step<N>) -> Vec<Deduction<N>> {
+    match sue_de_coq {
+        Step::SueDeCoq { line, region, intersection, line_als, region_als } => {
+            let values = grid.candidates_in(intersection);
+            let (line_values, region_values) = (grid.candidates_in(line_als), grid.candidates_in(region_als));
+            let line_eliminations = &line_values | &(&values & !&region_values);
+            let region_eliminations = &region_values | &(&values & !&line_values);
+            let line_cells = line & !(intersection | line_als);
+            let region_cells = region & !(intersection | region_als);
+            line_cells.iter().flat_map(|cell| (grid.candidates(cell) & &line_eliminations).into_iter().map(move |value| (cell, value)).collect::<Vec<_>>())
+                .chain(region_cells.iter().flat_map(|cell| (grid.candidates(cell) & &region_eliminations).into_iter().map(move |value| (cell, value)).collect::<Vec<_>>()))
+                .map(|(cell, value)| Deduction::Elimination(cell, value))
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, sue_de_coq: &Step<N>) -> String {
+    match sue_de_coq {
+        Step::SueDeCoq { line, region, intersection, line_als, region_als } => format!(
+            "Sue de Coq; {} in the intersection of {} and {} forms a locked set with {} in the line and {} in the region",
+            grid.cell_set_name(intersection), grid.cell_set_name(line), grid.cell_set_name(region),
+            grid.cell_set_name(line_als), grid.cell_set_name(region_als)
+        ),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_sue_de_coq() {
+        assert_deductions(Strategy::SueDeCoq, &[
+            (".........59.4...2...6.1...7.18...........8.56.......3.2..39.....6..2.3...39681..2", &[
+                "3 eliminated from r1c1", "7 eliminated from r1c1", "7 eliminated from r1c4", "3 eliminated from r1c6", "7 eliminated from r1c6",
+                "4 eliminated from r1c1", "4 eliminated from r3c1",
+            ]),
+            (".962.....3..5.7..9..........4...6........845...9...623....3.57...74.....5.39.....", &[
+                "4 eliminated from r1c5", "1 eliminated from r2c8", "8 eliminated from r2c8", "1 eliminated from r3c7", "8 eliminated from r3c7",
+                "1 eliminated from r3c8", "8 eliminated from r3c8", "8 eliminated from r3c9",
+            ]),
+            ("65....................3.27...36.9.4..4.1...62.1....9..1.428.....72..........45..3", &["2 eliminated from r6c6"]),
+        ]);
+    }
+}
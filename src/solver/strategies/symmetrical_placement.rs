@@ -0,0 +1,79 @@
+use itertools::Itertools;
+
+use crate::analyser::symmetry::automorphisms;
+use crate::grid::Grid;
+use crate::grid::candidate::{Candidate, CandidateSet};
+use crate::grid::cell::CellIdx;
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let automorphisms = if grid.has_unique_solution() { automorphisms(grid) } else { vec![] };
+    automorphisms.into_iter().map(|(symmetry, digits)| Step::SymmetricalPlacement { symmetry, digits })
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, symmetrical_placement: &Step<N>) -> Vec<Deduction<N>> {
+    match symmetrical_placement {
+        Step::SymmetricalPlacement { symmetry, digits } => {
+            let image = |Candidate(value): Candidate<N>| digits[value - 1];
+            grid.empty_cells().iter()
+                .flat_map(|cell| {
+                    let mapped_cell = symmetry.map_cell(cell);
+                    let remaining = CandidateSet::from_candidates(grid.candidates(cell).iter().filter(|&value| {
+                        if mapped_cell == cell { image(value) == value }
+                        else if grid.neighbours(cell).contains(mapped_cell) { image(value) != value && grid.has_candidate(mapped_cell, image(value)) }
+                        else { grid.has_candidate(mapped_cell, image(value)) }
+                    }));
+                    cell_deductions(grid, cell, &remaining)
+                })
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(_grid: &Grid<N>, symmetrical_placement: &Step<N>) -> String {
+    match symmetrical_placement {
+        Step::SymmetricalPlacement { symmetry, digits } => format!(
+            "Symmetrical Placement; the puzzle has {} symmetry with digits mapped {}",
+            symmetry.name(),
+            digits.iter().enumerate().map(|(idx, Candidate(value))| format!("{}→{}", idx + 1, value)).join(", ")
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn cell_deductions<const N: usize>(grid: &Grid<N>, cell: CellIdx<N>, remaining: &CandidateSet<N>) -> Vec<Deduction<N>> {
+    if remaining.len() == 1 && grid.num_candidates(cell) > 1 {
+        vec![Deduction::Placement(cell, remaining.first().unwrap())]
+    } else {
+        grid.candidates(cell).iter()
+            .filter(|&value| !remaining.contains(value))
+            .map(|value| Deduction::Elimination(cell, value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::strategies::{all_strategies, Step};
+    use crate::solver::{solve, SolveResult};
+
+    #[test]
+    fn test_symmetrical_placement_requires_uniqueness() {
+        let puzzle = "..1.9...22..3..1.......43........546.7.....3.465........76.......9..7..88...1.9..";
+
+        let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).unwrap();
+        let solve_details = solve(&mut grid, &all_strategies(9));
+        assert!(!solve_details.steps.iter().any(|(step, _)| matches!(step, Step::SymmetricalPlacement { .. })));
+
+        let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).unwrap();
+        grid.declare_unique_solution();
+        let solve_details = solve(&mut grid, &all_strategies(9));
+        assert_eq!(solve_details.result, SolveResult::Solved);
+        assert!(solve_details.steps.iter().any(|(step, _)| matches!(step, Step::SymmetricalPlacement { .. })));
+    }
+}
@@ -0,0 +1,124 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::{Candidate, CandidateSet};
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    let boxes: Vec<_> = grid.regions().iter()
+        .map(|region| (grid.intersecting_rows(region), grid.intersecting_columns(region)))
+        .filter(|(rows, columns)| rows.len() == 3 && columns.len() == 3)
+        .collect();
+    let rectangles: Vec<_> = boxes.into_iter().tuple_combinations()
+        .filter(|(box1, box2, box3, box4)| box1.0 == box2.0 && box3.0 == box4.0 && box1.1 == box3.1 && box2.1 == box4.1)
+        .map(|(box1, box2, box3, box4)| [box1, box2, box3, box4])
+        .collect();
+
+    rectangles.into_iter()
+        .cartesian_product(grid.all_values().iter().combinations(3).collect::<Vec<_>>())
+        .flat_map(move |(rectangle, values)| {
+            let values = CandidateSet::from_candidates(values);
+            rectangle.iter()
+                .map(|(rows, columns)| transversals(grid, rows, columns, &values))
+                .multi_cartesian_product()
+                .filter(|transversals| transversals.iter().filter(|(_, odd)| *odd).count() % 2 == 1)
+                .filter_map(|transversals| {
+                    let cells = CellSet::from_cells(transversals.iter().flat_map(|(cells, _)| cells.iter().copied()));
+                    let guardians = CellSet::from_cells(cells.iter().filter(|&cell| !(grid.candidates(cell) & !&values).is_empty()));
+                    (!guardians.is_empty()).then(|| Step::Tridagon { cells, values: values.clone(), guardians })
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(move |step| !deductions(grid, step).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, tridagon: &Step<N>) -> Vec<Deduction<N>> {
+    match tridagon {
+        Step::Tridagon { values, guardians, .. } => {
+            let guardian_candidates: Vec<_> = guardians.iter()
+                .flat_map(|cell| (grid.candidates(cell) & !values).iter().map(|value| (cell, value)).collect::<Vec<_>>())
+                .collect();
+            if let [(cell, value)] = guardian_candidates[..] {
+                return vec![Deduction::Placement(cell, value)];
+            }
+            let Some(&(first_cell, first_value)) = guardian_candidates.first() else { return vec![] };
+            grid.candidates(first_cell).iter().map(|value| (first_cell, value))
+                .chain(grid.cells_with_candidate_in(grid.neighbours(first_cell), first_value).iter().map(|cell| (cell, first_value)))
+                .filter(|&(cell, value)| guardian_candidates.iter().all(|&guardian| excludes(grid, (cell, value), guardian)))
+                .map(|(cell, value)| Deduction::Elimination(cell, value))
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, tridagon: &Step<N>) -> String {
+    match tridagon {
+        Step::Tridagon { cells, values, guardians } => format!(
+            "Tridagon; the cells {} cannot all be filled from {}, so one of the guardians {} holds another value",
+            grid.cell_set_name(cells), values, grid.cell_set_name(guardians)
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn transversals<const N: usize>(grid: &Grid<N>, rows: &[&CellSet<N>], columns: &[&CellSet<N>], values: &CandidateSet<N>) -> Vec<(Vec<CellIdx<N>>, bool)> {
+    (0 .. 3).permutations(3)
+        .filter_map(|permutation| {
+            let cells = (0 .. 3).map(|idx| (rows[idx] & columns[permutation[idx]]).first()).collect::<Option<Vec<_>>>()?;
+            let odd = permutation.iter().tuple_combinations().filter(|(a, b)| a > b).count() % 2 == 1;
+            cells.iter().all(|&cell| grid.value(cell).is_none() && grid.candidates(cell).intersects(values)).then_some((cells, odd))
+        })
+        .collect()
+}
+
+fn excludes<const N: usize>(grid: &Grid<N>, (cell, value): (CellIdx<N>, Candidate<N>), (guardian, guardian_value): (CellIdx<N>, Candidate<N>)) -> bool {
+    if cell == guardian { value != guardian_value } else { value == guardian_value && grid.neighbours(cell).contains(guardian) }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::generator::brute_force::BruteForceSolver;
+    use crate::grid::Grid;
+    use crate::grid::candidate::Candidate;
+    use crate::grid::variants::Classic;
+    use crate::solver::strategies::{Deduction, Strategy};
+
+    #[test]
+    fn test_tridagon() {
+        let puzzles = [
+            (
+                "...............6.8......39...268......79....2..9..74...1...497.6..2.98.45..87..26",
+                "Tridagon; the cells (r4c6, r4c8, r5c5, r5c7, r6c4, r6c9, r7c4, r7c9, r8c5, r8c8, r9c6, r9c7) cannot all be filled from (1, 3, 5), \
+                 so one of the guardians (r5c5) holds another value",
+                ["4 placed in r5c5"],
+            ),
+            (
+                ".................9....8..15..2..9....16.7....89.46.7.14.86......69.173..17.8.49.2",
+                "Tridagon; the cells (r4c2, r4c5, r5c1, r5c4, r6c3, r6c6, r7c2, r7c6, r8c1, r8c4, r9c3, r9c5) cannot all be filled from (2, 3, 5), \
+                 so one of the guardians (r4c2) holds another value",
+                ["4 placed in r4c2"],
+            ),
+        ];
+        for (puzzle, description, expected) in puzzles {
+            let grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+            let solution = BruteForceSolver::for_starting_grid(&grid).solution(&[0; 81]);
+
+            let steps: Vec<_> = Strategy::Tridagon.find_steps(&grid).collect();
+            assert_eq!(steps.len(), 1);
+            assert_eq!(steps[0].description(&grid), description);
+            let deductions = steps[0].deductions(&grid);
+            for &deduction in &deductions {
+                match deduction {
+                    Deduction::Placement(cell, value) => assert_eq!(value, Candidate(solution[cell.0])),
+                    Deduction::Elimination(cell, value) => assert_ne!(value, Candidate(solution[cell.0])),
+                    Deduction::Contradiction => panic!("Unexpected contradiction"),
+                }
+            }
+            assert_eq!(deductions.iter().map(|deduction| deduction.description(&grid)).collect::<Vec<_>>(), expected);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use itertools::{iproduct, Itertools};
+
+use crate::grid::Grid;
+use crate::grid::candidate::{Candidate, CandidateSet};
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step, UniqueRectangleFlavour};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>) -> impl Iterator<Item = Step<N>> + 'a {
+    rectangles::<N>()
+        .filter(move |_| grid.has_unique_solution())
+        .filter(move |corners| corners.iter().all(|cell| grid.is_empty(cell)))
+        .filter(move |corners| is_deadly_rectangle(grid, corners))
+        .flat_map(move |corners| {
+            let common_candidates = corners.iter().fold(grid.all_values(), |values, cell| values & grid.candidates(cell));
+            common_candidates.iter().tuple_combinations().map(|pair| (corners.clone(), pair)).collect::<Vec<_>>()
+        })
+        .flat_map(move |(corners, (x, y))| find_for_rectangle(grid, &corners, x, y))
+}
+
+pub fn rectangles<const N: usize>() -> impl Iterator<Item = CellSet<N>> {
+    iproduct!((0 .. N).tuple_combinations(), (0 .. N).tuple_combinations())
+        .map(|((r1, r2), (c1, c2))| CellSet::from_cells([(r1, c1), (r1, c2), (r2, c1), (r2, c2)].map(|(r, c)| CellIdx::from_row_and_col(r, c))))
+}
+
+pub fn is_deadly_rectangle<const N: usize>(grid: &Grid<N>, corners: &CellSet<N>) -> bool {
+    let corner_list: Vec<_> = corners.iter().collect();
+    grid.all_houses().iter().all(|house| matches!((house & corners).len(), 0 | 2))
+        && !grid.neighbours(corner_list[0]).contains(corner_list[3])
+        && !grid.neighbours(corner_list[1]).contains(corner_list[2])
+        && corner_list.iter().all(|&cell| {
+            grid.neighbours(cell) == &(CellSet::union(grid.all_houses().iter().filter(|house| house.contains(cell))) & !CellSet::from_cells([cell]))
+        })
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, unique_rectangle: &Step<N>) -> Vec<Deduction<N>> {
+    match unique_rectangle {
+        Step::UniqueRectangle { flavour, floor, roof, values } => match flavour {
+            UniqueRectangleFlavour::Type1 => eliminations(grid, roof, values),
+            UniqueRectangleFlavour::Type2 { value } | UniqueRectangleFlavour::Type5 { value } =>
+                eliminations(grid, &(grid.common_neighbours(roof) & !(floor | roof)), &CandidateSet::from_candidates([*value])),
+            UniqueRectangleFlavour::Type3 { house, cells, values: subset_values } =>
+                eliminations(grid, &(house & !(roof | cells)), subset_values),
+            UniqueRectangleFlavour::Type4 { value, .. } | UniqueRectangleFlavour::Type6 { value } =>
+                eliminations(grid, roof, &CandidateSet::from_candidates([*value])),
+            UniqueRectangleFlavour::Hidden { value } => {
+                eliminations(grid, &far_corner(grid, floor, roof), &CandidateSet::from_candidates([*value]))
+            },
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, unique_rectangle: &Step<N>) -> String {
+    match unique_rectangle {
+        Step::UniqueRectangle { flavour, floor, roof, values } => {
+            let detail = match flavour {
+                UniqueRectangleFlavour::Type1 => format!("the roof {} must contain another candidate", grid.cell_set_name(roof)),
+                UniqueRectangleFlavour::Type2 { value } | UniqueRectangleFlavour::Type5 { value } =>
+                    format!("the roof {} must contain {}", grid.cell_set_name(roof), value.0),
+                UniqueRectangleFlavour::Type3 { cells, values: subset_values, .. } =>
+                    format!("the roof {} forms a locked set {} with {}", grid.cell_set_name(roof), subset_values, grid.cell_set_name(cells)),
+                UniqueRectangleFlavour::Type4 { house, value } =>
+                    format!("{} is locked into the roof {} in {}", value.0, grid.cell_set_name(roof), grid.cell_set_name(house)),
+                UniqueRectangleFlavour::Type6 { value } =>
+                    format!("{} must occupy the diagonal {}", value.0, grid.cell_set_name(floor)),
+                UniqueRectangleFlavour::Hidden { value } => format!(
+                    "{} is locked into the rectangle around {}, so {} is removed from it",
+                    (values & !CandidateSet::from_candidates([*value])).first().unwrap().0, grid.cell_set_name(&far_corner(grid, floor, roof)), value.0
+                ),
+            };
+            format!(
+                "Unique Rectangle {}; corners {} would form a deadly pattern on {}, so {}",
+                name(flavour), grid.cell_set_name(&(floor | roof)), values, detail
+            )
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn name<const N: usize>(flavour: &UniqueRectangleFlavour<N>) -> &str {
+    match flavour {
+        UniqueRectangleFlavour::Type1 => "Type 1",
+        UniqueRectangleFlavour::Type2 { .. } => "Type 2",
+        UniqueRectangleFlavour::Type3 { .. } => "Type 3",
+        UniqueRectangleFlavour::Type4 { .. } => "Type 4",
+        UniqueRectangleFlavour::Type5 { .. } => "Type 5",
+        UniqueRectangleFlavour::Type6 { .. } => "Type 6",
+        UniqueRectangleFlavour::Hidden { .. } => "(Hidden)",
+    }
+}
+
+fn find_for_rectangle<const N: usize>(grid: &Grid<N>, corners: &CellSet<N>, x: Candidate<N>, y: Candidate<N>) -> Vec<Step<N>> {
+    let values = CandidateSet::from_candidates([x, y]);
+    let floor = grid.cells_with_exact_candidates_in(corners, &values);
+    let roof = corners & !&floor;
+    if roof.is_empty() { return vec![]; }
+
+    let extras = &grid.candidates_in(&roof) & !&values;
+    let roof_houses: Vec<_> = grid.all_houses_containing(&roof).into_iter().cloned().collect();
+    let step = |flavour| Step::UniqueRectangle { flavour, floor: floor.clone(), roof: roof.clone(), values: values.clone() };
+    let mut steps = Vec::new();
+
+    if roof.len() == 1 {
+        steps.push(step(UniqueRectangleFlavour::Type1));
+    }
+
+    if extras.len() == 1 && roof.len() > 1 {
+        let value = extras.first().unwrap();
+        steps.push(step(if roof_houses.is_empty() { UniqueRectangleFlavour::Type5 { value } } else { UniqueRectangleFlavour::Type2 { value } }));
+    }
+
+    if roof.len() == 2 {
+        for house in &roof_houses {
+            let others = grid.empty_cells_in(&(house & !&roof));
+            for degree in 1 ..= N / 2 - 1 {
+                for cells in others.iter().combinations(degree).map(CellSet::from_cells) {
+                    let subset_values = &grid.candidates_in(&cells) | &extras;
+                    if subset_values.len() == degree + 1 {
+                        steps.push(step(UniqueRectangleFlavour::Type3 { house: house.clone(), cells, values: subset_values }));
+                    }
+                }
+            }
+
+            for (locked, other) in [(x, y), (y, x)] {
+                if grid.cells_with_candidate_in(house, locked) == roof {
+                    steps.push(step(UniqueRectangleFlavour::Type4 { house: house.clone(), value: other }));
+                }
+            }
+        }
+
+        if roof_houses.is_empty() {
+            for value in values.iter() {
+                if lines_through(grid, corners).iter().all(|line| grid.cells_with_candidate_in(line, value) == line & corners) {
+                    steps.push(step(UniqueRectangleFlavour::Type6 { value }));
+                }
+            }
+        }
+    }
+
+    if roof.len() > 1 {
+        for floor_cell in floor.iter() {
+            let roof = corners & !CellSet::from_cells([floor_cell]);
+            let far_corner = far_corner(grid, &CellSet::from_cells([floor_cell]), &roof);
+            let far_lines: Vec<_> = lines_through(grid, corners).into_iter().filter(|line| line.contains_all(&far_corner)).collect();
+            for (locked, other) in [(x, y), (y, x)] {
+                if far_lines.iter().all(|line| grid.cells_with_candidate_in(line, locked) == line & corners) {
+                    let flavour = UniqueRectangleFlavour::Hidden { value: other };
+                    steps.push(Step::UniqueRectangle { flavour, floor: CellSet::from_cells([floor_cell]), roof: roof.clone(), values: values.clone() });
+                }
+            }
+        }
+    }
+
+    steps
+}
+
+fn far_corner<const N: usize>(grid: &Grid<N>, floor: &CellSet<N>, roof: &CellSet<N>) -> CellSet<N> {
+    roof.filter(|&cell| !floor.iter().any(|floor_cell| grid.neighbours(floor_cell).contains(cell)))
+}
+
+fn lines_through<const N: usize>(grid: &Grid<N>, corners: &CellSet<N>) -> Vec<CellSet<N>> {
+    grid.intersecting_rows(corners).into_iter().chain(grid.intersecting_columns(corners)).cloned().collect()
+}
+
+fn eliminations<const N: usize>(grid: &Grid<N>, cells: &CellSet<N>, values: &CandidateSet<N>) -> Vec<Deduction<N>> {
+    cells.iter()
+        .flat_map(|cell| (grid.candidates(cell) & values).into_iter().map(move |value| Deduction::Elimination(cell, value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::solver::strategies::Strategy;
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_unique_rectangles() {
+        assert_deductions(Strategy::UniqueRectangle, &[
+            ("1..68......27.4...68.....259.......8.1.....3.5....92......3.974....7..1.4.1......", &["4 eliminated from r1c3", "7 eliminated from r1c3"]),
+            (".36.4......8...3..1....8.4..7..6.....63.95.1...5.....7......8.3.8...29..5..9...7.", &[
+                "2 eliminated from r1c7", "2 eliminated from r3c7", "2 eliminated from r3c9",
+            ]),
+            (".35........43...8..91...5....8...6...1...92....6.2..91.....7....8..51..3....96.4.", &["6 eliminated from r1c1", "6 eliminated from r3c1"]),
+            (".45...8..2......64.7...1..2.56..9..192.6..54.....1....8..1.39.......5....3..9...8", &["7 eliminated from r6c8"]),
+            (".......5..4...3..7..75..29..7.24.5..31..7....2...39.1..3..6..........9..42.....6.", &["5 eliminated from r8c5"]),
+        ]);
+    }
+}
@@ -0,0 +1,159 @@
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::grid::candidate::CandidateSet;
+use crate::grid::cell::{CellIdx, CellSet};
+
+use super::{Deduction, Step};
+
+pub fn find<'a, const N: usize>(grid: &'a Grid<N>, max_size: usize) -> impl Iterator<Item = Step<N>> + 'a {
+    (3 ..= max_size)
+        .flat_map(move |size| grid.empty_cells().into_iter()
+            .filter(move |&pivot| grid.num_candidates(pivot) <= size)
+            .flat_map(move |pivot| {
+                let wing_cells: Vec<_> = grid.empty_cells_in(grid.neighbours(pivot)).iter()
+                    .filter(|&cell| grid.num_candidates(cell) <= size)
+                    .collect();
+                pincer_sets(grid, &wing_cells, size - 1, grid.candidates(pivot).clone(), size).into_iter()
+                    .map(move |pincers| Step::Wing { pivot, pincers: CellSet::from_cells(pincers) })
+            }))
+        .filter(move |wing| is_canonical(grid, wing))
+        .filter(move |wing| !deductions(grid, wing).is_empty())
+}
+
+pub fn deductions<const N: usize>(grid: &Grid<N>, wing: &Step<N>) -> Vec<Deduction<N>> {
+    match wing {
+        Step::Wing { pivot, pincers } => {
+            let cells = pincers | &CellSet::from_cells([*pivot]);
+            eliminated_values(grid, &cells).iter()
+                .flat_map(|value| {
+                    let value_cells = grid.cells_with_candidate_in(&cells, value);
+                    let elimination_cells = grid.cells_with_candidate_in(&grid.common_neighbours(&value_cells), value) & !&cells;
+                    elimination_cells.iter().map(move |cell| Deduction::Elimination(cell, value)).collect::<Vec<_>>()
+                })
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
+pub fn description<const N: usize>(grid: &Grid<N>, wing: &Step<N>) -> String {
+    match wing {
+        Step::Wing { pivot, pincers } => format!(
+            "{}; pivot {} and pincers ({}) eliminate {} from common neighbours",
+            wing_name(pincers.len() + 1, grid.num_candidates(*pivot)), grid.cell_name(*pivot), pincers.iter().map(|cell| grid.cell_name(cell)).join(", "),
+            eliminated_values(grid, &(pincers | &CellSet::from_cells([*pivot]))),
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn pincer_sets<const N: usize>(grid: &Grid<N>, cells: &[CellIdx<N>], num_pincers: usize, candidates: CandidateSet<N>, size: usize) -> Vec<Vec<CellIdx<N>>> {
+    if num_pincers == 0 {
+        return if candidates.len() == size { vec![vec![]] } else { vec![] };
+    }
+
+    (0 .. cells.len())
+        .flat_map(|idx| {
+            let candidates = &candidates | grid.candidates(cells[idx]);
+            if candidates.len() > size { return vec![]; }
+            pincer_sets(grid, &cells[idx + 1 ..], num_pincers - 1, candidates, size).into_iter()
+                .map(|mut pincers| { pincers.insert(0, cells[idx]); pincers })
+                .collect()
+        })
+        .collect()
+}
+
+fn is_canonical<const N: usize>(grid: &Grid<N>, wing: &Step<N>) -> bool {
+    match wing {
+        Step::Wing { pivot, pincers } => {
+            let cells = pincers | &CellSet::from_cells([*pivot]);
+            grid.all_houses_containing(&cells).is_empty()
+                && cells.iter().find(|&cell| (&cells & !&CellSet::from_cells([cell])).iter().all(|other| grid.neighbours(cell).contains(other))) == Some(*pivot)
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn eliminated_values<const N: usize>(grid: &Grid<N>, cells: &CellSet<N>) -> CandidateSet<N> {
+    let candidates = grid.candidates_in(cells);
+    let unrestricted: Vec<_> = candidates.iter()
+        .filter(|&value| {
+            grid.cells_with_candidate_in(cells, value).iter().tuple_combinations()
+                .any(|(cell1, cell2)| !grid.neighbours(cell1).contains(cell2))
+        })
+        .collect();
+
+    match unrestricted[..] {
+        [] => candidates,
+        [value] => CandidateSet::from_candidates([value]),
+        _ => CandidateSet::empty(),
+    }
+}
+
+fn wing_name(size: usize, pivot_size: usize) -> &'static str {
+    match (size, pivot_size) {
+        (3, 2) => "Y-Wing", (3, _) => "XYZ-Wing", (4, _) => "WXYZ-Wing", (5, _) => "VWXYZ-Wing",
+        (6, _) => "UVWXYZ-Wing", (7, _) => "TUVWXYZ-Wing",
+        _ => "Bent Subset",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::grid::Grid;
+    use crate::grid::variants::Classic;
+    use crate::solver::solve;
+    use crate::solver::strategies::{Strategy, SINGLES};
+    use crate::solver::strategies::tests::assert_deductions;
+
+    #[test]
+    fn test_y_and_xyz_wings() {
+        assert_deductions(Strategy::Wing(3), &[
+            ("...638...2.7.............4..52.17.........9..4..38...6....41...9...5.8.4.7......1", &["1 eliminated from r3c3"]),
+            (".8....4..4.5...3826.......9..8..1.5....9......3.....769..81......4..57...6.2...9.", &["5 eliminated from r6c4"]),
+        ]);
+    }
+
+    #[test]
+    fn test_four_cell_wings() {
+        assert_deductions(Strategy::Wing(4), &[
+            (".......5..4...3..7..75..29..7.24.5..31..7....2...39.1..3..6..........9..42.....6.", &["8 eliminated from r9c4"]),
+            ("....27.36......7..8...5....3.......9....8....26.....47.1...3....349.5.....6.72...", &[
+                "1 eliminated from r1c3", "4 eliminated from r1c2", "5 eliminated from r5c1", "9 eliminated from r5c1",
+            ]),
+        ]);
+    }
+
+    #[test]
+    fn test_five_cell_wings() {
+        assert_deductions(Strategy::Wing(5), &[
+            (".5....3.8.6..4.......2....73.4..1........84.....95.7....1.....662..3..5..9...5...", &["9 eliminated from r1c3", "9 eliminated from r3c6"]),
+            ("..8.1......4..3...13....4.53...5..98...6....7...3...5.5........9..18..6..4....91.", &["2 eliminated from r1c7"]),
+        ]);
+    }
+
+    #[test]
+    fn test_wings_below_three_cells_find_nothing() {
+        let grid = Grid::<9>::empty_classic();
+        assert_eq!(Strategy::Wing(0).find_steps(&grid).count(), 0);
+        assert_eq!(Strategy::Wing(2).find_steps(&grid).count(), 0);
+    }
+
+    #[test]
+    fn test_wing_names() {
+        let puzzles = [
+            (3, ".8....4..4.5...3826.......9..8..1.5....9......3.....769..81......4..57...6.2...9.", "Y-Wing; pivot r1c1 and pincers (r1c4, r6c1) eliminate (5) from common neighbours"),
+            (3, "...638...2.7.............4..52.17.........9..4..38...6....41...9...5.8.4.7......1", "XYZ-Wing; pivot r1c3 and pincers (r1c1, r6c3) eliminate (1) from common neighbours"),
+            (4, ".3...5....8...6..2..2..3.9....61..4.......9..4269..............5.9..428.7.....1.3", "Y-Wing; pivot r5c2 and pincers (r4c3, r5c6) eliminate (7) from common neighbours"),
+            (4, ".......5..4...3..7..75..29..7.24.5..31..7....2...39.1..3..6..........9..42.....6.", "WXYZ-Wing; pivot r2c4 and pincers (r2c5, r5c4, r6c4) eliminate (1, 6, 8, 9) from common neighbours"),
+        ];
+        for (size, puzzle, expected) in puzzles {
+            let mut grid = Grid::<9>::from_empty_grid_and_string(Grid::empty_classic(), puzzle).expect("Failed to parse grid");
+            solve(&mut grid, SINGLES);
+            let step = Strategy::Wing(size).find_steps(&grid).find(|step| !step.deductions(&grid).is_empty()).expect("No wing found");
+            assert_eq!(step.description(&grid), expected);
+        }
+    }
+}